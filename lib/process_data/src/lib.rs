@@ -1,15 +1,41 @@
 use anyhow::{anyhow, Context, Result};
+use glob::glob;
 use nparse::KVStrToJson;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::time::Duration;
 use std::{path::PathBuf, time::SystemTime};
+use tokio::task::JoinSet;
+
+/// Reads from procfs can block for a long time, e.g. on a hung NFS mount or for
+/// processes in uninterruptible sleep, so anything taking longer than this is
+/// skipped for the current refresh
+pub const READ_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Argument that tells the process helper to count the open file descriptors of
+/// the processes, see `ProcessData::all()`
+pub const OPEN_FDS_ARG: &str = "--open-fds";
 
 static PAGESIZE: Lazy<usize> = Lazy::new(sysconf::pagesize);
 
+/// The network namespace the process gathering the data lives in, which is the
+/// host's namespace unless we're containerized ourselves
+static OWN_NET_NAMESPACE: Lazy<Option<u64>> = Lazy::new(|| {
+    std::fs::read_link("/proc/self/ns/net")
+        .ok()
+        .and_then(parse_namespace)
+});
+
 static UID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Uid:\s*(\d+)").unwrap());
 
+static EFFECTIVE_UID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"Uid:\s*\d+\s+(\d+)").unwrap());
+
+static SWAP_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"VmSwap:\s*(\d+)\s*kB").unwrap());
+
+static CAP_EFF_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"CapEff:\s*([0-9a-fA-F]+)").unwrap());
+
 #[derive(Debug, Clone, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Containerization {
     #[default]
@@ -17,6 +43,29 @@ pub enum Containerization {
     Flatpak,
 }
 
+/// What kind of unit a process' cgroup belongs to, derived from its cgroup v2
+/// path
+#[derive(Debug, Clone, Copy, Default, Hash, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CgroupKind {
+    #[default]
+    Other,
+    SystemService,
+    UserService,
+    Scope,
+    Flatpak,
+    Podman,
+    Docker,
+}
+
+/// The data of all processes that could be read, along with the PIDs of
+/// processes whose reads exceeded `READ_TIMEOUT` and should therefore keep
+/// their last-known values
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AllProcessData {
+    pub processes: Vec<ProcessData>,
+    pub timed_out: Vec<i32>,
+}
+
 /// Data that could be transferred using `resources-processes`, separated from
 /// `Process` mainly due to `Icon` not being able to derive `Serialize` and
 /// `Deserialize`.
@@ -24,18 +73,57 @@ pub enum Containerization {
 pub struct ProcessData {
     pub pid: i32,
     pub uid: u32,
+    /// The user whose privileges the process has, which differs from `uid`
+    /// e.g. for setuid binaries
+    pub effective_uid: u32,
     proc_path: PathBuf,
     pub comm: String,
     pub commandline: String,
+    /// The target of /proc/[pid]/exe, `None` for kernel threads or if we're
+    /// not allowed to look at it
+    pub exe: Option<String>,
     pub cpu_time: u64,
+    pub user_cpu_time: u64,
+    pub system_cpu_time: u64,
     pub cpu_time_timestamp: u64,
+    /// Time after boot the process was started at, in clock ticks
+    pub start_time: u64,
+    /// The nice value, from 19 (lowest priority) to -20 (highest priority)
+    pub nice: i32,
+    /// Resident memory that isn't shared with other processes
     pub memory_usage: usize,
+    /// Resident memory that's backed by files (e.g. shared libraries) and may
+    /// be shared with other processes
+    pub shared_memory_usage: usize,
+    pub swap_usage: usize,
+    /// Bit mask of the capabilities the kernel checks the process' privileged
+    /// operations against, bit n being the capability with number n
+    pub effective_capabilities: u64,
     pub cgroup: Option<String>,
+    pub cgroup_kind: CgroupKind,
     pub containerization: Containerization,
     pub read_bytes: Option<u64>,
     pub read_bytes_timestamp: Option<u64>,
     pub write_bytes: Option<u64>,
     pub write_bytes_timestamp: Option<u64>,
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    pub state: char,
+    pub wchan: Option<String>,
+    /// Amount of open file descriptors, `None` if they weren't asked for or
+    /// we're not allowed to look into /proc/[pid]/fd
+    pub open_fds: Option<usize>,
+    /// Inode of the network namespace of the process, `None` if we're not
+    /// allowed to look at it
+    pub net_namespace: Option<u64>,
+    /// Bytes received and sent by the whole network namespace of the process
+    /// according to /proc/[pid]/net/netstat. The kernel doesn't attribute
+    /// traffic to individual processes, so this is only gathered for processes
+    /// in a namespace other than ours (e.g. containers or sandboxes), where
+    /// the namespace's traffic is a reasonable estimate. All processes
+    /// sharing a namespace have the same value.
+    pub net_bytes: Option<u64>,
+    pub net_bytes_timestamp: Option<u64>,
 }
 
 impl ProcessData {
@@ -72,9 +160,54 @@ impl ProcessData {
         }
     }
 
-    async fn get_uid(proc_path: &PathBuf) -> Result<u32> {
-        let status = tokio::fs::read_to_string(proc_path.join("status")).await?;
-        if let Some(captures) = UID_REGEX.captures(&status) {
+    fn classify_cgroup<S: AsRef<str>>(cgroup: S) -> CgroupKind {
+        let Some(path) = cgroup
+            .as_ref()
+            .split('\n')
+            .find_map(|line| line.strip_prefix("0::"))
+        else {
+            return CgroupKind::Other;
+        };
+
+        // containers first because their scopes are also below a service or slice
+        if path.contains("/libpod-") {
+            CgroupKind::Podman
+        } else if path.contains("/docker-") || path.contains("/docker/") {
+            CgroupKind::Docker
+        } else if path.contains("/app-flatpak-") {
+            CgroupKind::Flatpak
+        } else if path.ends_with(".scope") {
+            CgroupKind::Scope
+        } else if path.ends_with(".service") {
+            if path.contains("/user@") {
+                CgroupKind::UserService
+            } else {
+                CgroupKind::SystemService
+            }
+        } else {
+            CgroupKind::Other
+        }
+    }
+
+    /// Sums up `InOctets` and `OutOctets` of the `IpExt` section of
+    /// /proc/[pid]/net/netstat, which consists of a line of names followed by
+    /// a line of values
+    fn get_net_octets(netstat: &str) -> Option<u64> {
+        let mut ip_ext = netstat.lines().filter(|line| line.starts_with("IpExt:"));
+        let names = ip_ext.next()?.split_whitespace();
+        let values = ip_ext.next()?.split_whitespace();
+
+        let mut octets = None;
+        for (name, value) in names.zip(values) {
+            if name == "InOctets" || name == "OutOctets" {
+                octets = Some(octets.unwrap_or(0) + value.parse::<u64>().ok()?);
+            }
+        }
+        octets
+    }
+
+    fn get_uid(status: &str) -> Result<u32> {
+        if let Some(captures) = UID_REGEX.captures(status) {
             let first_num_str = captures.get(1).context("no uid found")?;
             first_num_str
                 .as_str()
@@ -85,7 +218,78 @@ impl ProcessData {
         }
     }
 
-    pub async fn try_from_path(proc_path: PathBuf) -> Result<Self> {
+    /// Returns the second field of the "Uid:" line, falls back to `uid` if
+    /// it's missing
+    fn get_effective_uid(status: &str, uid: u32) -> u32 {
+        EFFECTIVE_UID_REGEX
+            .captures(status)
+            .and_then(|captures| captures.get(1))
+            .and_then(|effective_uid| effective_uid.as_str().parse().ok())
+            .unwrap_or(uid)
+    }
+
+    /// Returns the amount of bytes of the process that are swapped out.
+    ///
+    /// This is the same value as the sum of the "Swap:" lines in smaps_rollup,
+    /// but the kernel already keeps track of it for the status file, so it
+    /// doesn't require walking all memory mappings of the process
+    fn get_swap_usage(status: &str) -> usize {
+        SWAP_REGEX
+            .captures(status)
+            .and_then(|captures| captures.get(1))
+            .and_then(|kilobytes| kilobytes.as_str().parse::<usize>().ok())
+            .map_or(0, |kilobytes| kilobytes * 1024)
+    }
+
+    /// Returns the effective capabilities of the process, which are given as a
+    /// hexadecimal bit mask in the "CapEff:" line
+    fn get_effective_capabilities(status: &str) -> u64 {
+        CAP_EFF_REGEX
+            .captures(status)
+            .and_then(|captures| captures.get(1))
+            .and_then(|mask| u64::from_str_radix(mask.as_str(), 16).ok())
+            .unwrap_or(0)
+    }
+
+    /// Gathers the data of all currently running processes. Their open file
+    /// descriptors are only counted if `count_open_fds` is set because that
+    /// means reading another directory per process.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if there are problems traversing procfs
+    pub async fn all(count_open_fds: bool) -> Result<AllProcessData> {
+        let mut tasks = JoinSet::new();
+
+        for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
+            tasks.spawn(async move {
+                let pid = entry
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.parse::<i32>().ok());
+                (
+                    pid,
+                    tokio::time::timeout(READ_TIMEOUT, Self::try_from_path(entry, count_open_fds))
+                        .await,
+                )
+            });
+        }
+
+        let mut all_process_data = AllProcessData::default();
+        while let Some(task) = tasks.join_next().await {
+            // Unwrap is fine because the runtime stays alive
+            // Nothing should be able to panic here
+            match task.unwrap() {
+                (_, Ok(Ok(data))) => all_process_data.processes.push(data),
+                (Some(pid), Err(_)) => all_process_data.timed_out.push(pid),
+                _ => (),
+            }
+        }
+
+        Ok(all_process_data)
+    }
+
+    pub async fn try_from_path(proc_path: PathBuf, count_open_fds: bool) -> Result<Self> {
         // Stat
         let shared_proc_path = Arc::new(proc_path.clone());
         let stat = tokio::task::spawn(async move {
@@ -100,15 +304,15 @@ impl ProcessData {
 
         // Comm
         let shared_proc_path = Arc::new(proc_path.clone());
-        let comm = tokio::task::spawn(async move {
-            tokio::fs::read_to_string(shared_proc_path.join("comm")).await
-        });
+        let comm =
+            tokio::task::spawn(async move { read_sanitized(shared_proc_path.join("comm")).await });
 
         // Cmdline
         let shared_proc_path = Arc::new(proc_path.clone());
-        let commandline = tokio::task::spawn(async move {
-            tokio::fs::read_to_string(shared_proc_path.join("cmdline")).await
-        });
+        let commandline =
+            tokio::task::spawn(
+                async move { read_sanitized(shared_proc_path.join("cmdline")).await },
+            );
 
         // Cgroup
         let shared_proc_path = Arc::new(proc_path.clone());
@@ -116,12 +320,24 @@ impl ProcessData {
             tokio::fs::read_to_string(shared_proc_path.join("cgroup")).await
         });
 
+        // Wchan
+        let shared_proc_path = Arc::new(proc_path.clone());
+        let wchan = tokio::task::spawn(async move {
+            tokio::fs::read_to_string(shared_proc_path.join("wchan")).await
+        });
+
         // IO
         let shared_proc_path = Arc::new(proc_path.clone());
         let io = tokio::task::spawn(async move {
             tokio::fs::read_to_string(shared_proc_path.join("io")).await
         });
 
+        // Open file descriptors
+        let shared_proc_path = Arc::new(proc_path.clone());
+        let open_fds = count_open_fds.then(|| {
+            tokio::task::spawn(async move { count_entries(shared_proc_path.join("fd")).await })
+        });
+
         let stat = stat.await??;
         let statm = statm.await??;
         let comm = comm.await??;
@@ -135,7 +351,11 @@ impl ProcessData {
             .ok_or_else(|| anyhow!(""))?
             .parse()?;
 
-        let uid = Self::get_uid(&proc_path).await?;
+        let status = tokio::fs::read_to_string(proc_path.join("status")).await?;
+        let uid = Self::get_uid(&status)?;
+        let effective_uid = Self::get_effective_uid(&status, uid);
+        let swap_usage = Self::get_swap_usage(&status);
+        let effective_capabilities = Self::get_effective_capabilities(&status);
 
         let stat = stat
             .split(' ')
@@ -147,18 +367,66 @@ impl ProcessData {
             .map(std::string::ToString::to_string)
             .collect::<Vec<_>>();
 
-        let comm = comm.replace('\n', "");
+        let exe = tokio::fs::read_link(proc_path.join("exe"))
+            .await
+            .ok()
+            .map(|exe| exe.to_string_lossy().to_string());
+
+        let user_cpu_time = stat[13].parse::<u64>()?;
+        let system_cpu_time = stat[14].parse::<u64>()?;
+        let cpu_time = user_cpu_time + system_cpu_time;
+
+        let state = stat[2].chars().next().unwrap_or('?');
+
+        let nice = stat[18].parse::<i32>()?;
+
+        let start_time = stat[21].parse::<u64>()?;
 
-        let cpu_time = stat[13].parse::<u64>()? + stat[14].parse::<u64>()?;
+        let minor_faults = stat[9].parse::<u64>()?;
+        let major_faults = stat[11].parse::<u64>()?;
 
         let cpu_time_timestamp = SystemTime::now()
             .duration_since(SystemTime::UNIX_EPOCH)?
             .as_millis() as u64;
 
-        let memory_usage = (statm[1].parse::<usize>()? - statm[2].parse::<usize>()?) * *PAGESIZE;
+        let shared_memory_usage = statm[2].parse::<usize>()? * *PAGESIZE;
+        let memory_usage = statm[1].parse::<usize>()? * *PAGESIZE - shared_memory_usage;
 
+        let cgroup_kind = Self::classify_cgroup(&cgroup);
         let cgroup = Self::sanitize_cgroup(cgroup);
 
+        // "0" means that the process isn't waiting on anything
+        let wchan = wchan
+            .await?
+            .ok()
+            .map(|wchan| wchan.trim().to_string())
+            .filter(|wchan| !wchan.is_empty() && wchan != "0");
+
+        let open_fds = match open_fds {
+            Some(open_fds) => open_fds.await?,
+            None => None,
+        };
+
+        let net_namespace = tokio::fs::read_link(proc_path.join("ns/net"))
+            .await
+            .ok()
+            .and_then(parse_namespace);
+
+        let (mut net_bytes, mut net_bytes_timestamp) = (None, None);
+        if net_namespace.is_some() && net_namespace != *OWN_NET_NAMESPACE {
+            net_bytes = tokio::fs::read_to_string(proc_path.join("net/netstat"))
+                .await
+                .ok()
+                .and_then(|netstat| Self::get_net_octets(&netstat));
+            if net_bytes.is_some() {
+                net_bytes_timestamp = Some(
+                    SystemTime::now()
+                        .duration_since(SystemTime::UNIX_EPOCH)?
+                        .as_millis() as u64,
+                );
+            }
+        }
+
         let containerization = match &proc_path.join("root").join(".flatpak-info").exists() {
             true => Containerization::Flatpak,
             false => Containerization::None,
@@ -208,18 +476,88 @@ impl ProcessData {
         Ok(Self {
             pid,
             uid,
+            effective_uid,
             comm,
             commandline,
+            exe,
             cpu_time,
+            user_cpu_time,
+            system_cpu_time,
             cpu_time_timestamp,
+            start_time,
+            nice,
             memory_usage,
+            shared_memory_usage,
+            swap_usage,
+            effective_capabilities,
             cgroup,
+            cgroup_kind,
             proc_path,
             containerization,
             read_bytes,
             read_bytes_timestamp,
             write_bytes,
             write_bytes_timestamp,
+            minor_faults,
+            major_faults,
+            state,
+            wchan,
+            open_fds,
+            net_namespace,
+            net_bytes,
+            net_bytes_timestamp,
         })
     }
 }
+
+/// Returns the amount of entries in the directory at `path`, `None` if it
+/// can't be read
+async fn count_entries(path: PathBuf) -> Option<usize> {
+    let mut entries = tokio::fs::read_dir(path).await.ok()?;
+    let mut count = 0;
+    while let Ok(Some(_)) = entries.next_entry().await {
+        count += 1;
+    }
+    Some(count)
+}
+
+/// Reads a file that the process itself controls the content of, like its name
+/// or commandline, and sanitizes it using `sanitize()`
+async fn read_sanitized(path: PathBuf) -> std::io::Result<String> {
+    let bytes = tokio::fs::read(path).await?;
+    Ok(sanitize(&bytes))
+}
+
+/// Contents of files like comm aren't necessarily valid UTF-8, so invalid
+/// sequences are replaced instead of failing, and control characters (e.g. the
+/// trailing newline of comm) are removed so they can't mess up displaying or
+/// searching them. NUL is kept because it separates the arguments of the
+/// commandline.
+fn sanitize(bytes: &[u8]) -> String {
+    String::from_utf8_lossy(bytes)
+        .chars()
+        .filter(|c| *c == '\0' || !c.is_control())
+        .collect()
+}
+
+/// Parses the target of a /proc/[pid]/ns/* link, e.g. "net:[4026531840]"
+fn parse_namespace(link: PathBuf) -> Option<u64> {
+    let link = link.to_string_lossy();
+    link.split_once(":[")?.1.strip_suffix(']')?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sanitize_comm() {
+        assert_eq!(sanitize(b"fire\xfffox\n"), "fire\u{FFFD}fox");
+        assert_eq!(sanitize(b"evil\nname\x1b[31m\n"), "evilname[31m");
+    }
+
+    #[test]
+    fn sanitize_keeps_nul() {
+        assert_eq!(sanitize(b"ls\0-l\0"), "ls\0-l\0");
+    }
+}
@@ -1,3 +1,5 @@
+use std::num::ParseIntError;
+
 use log::{debug, info};
 
 use adw::{prelude::*, subclass::prelude::*};
@@ -18,6 +20,7 @@ mod imp {
     #[derive(Debug, Default)]
     pub struct Application {
         pub window: OnceLock<WeakRef<MainWindow>>,
+        pub pid: OnceLock<i32>,
     }
 
     #[glib::object_subclass]
@@ -46,6 +49,10 @@ mod imp {
                 .set(window.downgrade())
                 .expect("Window already set.");
 
+            if let Some(pid) = self.pid.get() {
+                window.open_process_dialog_on_start(*pid);
+            }
+
             app.main_window().present();
         }
 
@@ -111,6 +118,20 @@ impl Application {
         }));
         self.add_action(&action_settings);
 
+        // Save Snapshot
+        let action_save_snapshot = gio::SimpleAction::new("save-snapshot", None);
+        action_save_snapshot.connect_activate(clone!(@weak self as app => move |_, _| {
+            app.main_window().save_snapshot();
+        }));
+        self.add_action(&action_save_snapshot);
+
+        // Export Applications
+        let action_export_applications = gio::SimpleAction::new("export-applications", None);
+        action_export_applications.connect_activate(clone!(@weak self as app => move |_, _| {
+            app.main_window().export_applications();
+        }));
+        self.add_action(&action_export_applications);
+
         // About
         let action_about = gio::SimpleAction::new("about", None);
         action_about.connect_activate(clone!(@weak self as app => move |_, _| {
@@ -181,7 +202,38 @@ impl Application {
         info!("Version: {} ({})", VERSION, PROFILE);
         info!("Datadir: {}", PKGDATADIR);
 
-        ApplicationExtManual::run(self);
+        let mut args = std::env::args().collect::<Vec<_>>();
+        match Self::take_pid_argument(&mut args) {
+            Some(Ok(pid)) => {
+                let _ = self.imp().pid.set(pid);
+            }
+            Some(Err(_)) => {
+                eprintln!("--pid expects a process ID, e.g. --pid 1234");
+                std::process::exit(1);
+            }
+            None => {}
+        }
+
+        ApplicationExtManual::run_with_args(self, &args);
+    }
+
+    /// Removes `--pid <PID>` or `--pid=<PID>` from `args` (GApplication
+    /// would reject it otherwise) and returns the parsed PID
+    fn take_pid_argument(args: &mut Vec<String>) -> Option<Result<i32, ParseIntError>> {
+        let position = args
+            .iter()
+            .position(|arg| arg == "--pid" || arg.starts_with("--pid="))?;
+        let arg = args.remove(position);
+
+        let pid = if let Some(pid) = arg.strip_prefix("--pid=") {
+            pid.to_string()
+        } else if position < args.len() {
+            args.remove(position)
+        } else {
+            String::new()
+        };
+
+        Some(pid.parse())
     }
 }
 
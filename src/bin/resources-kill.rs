@@ -2,24 +2,42 @@ use std::env;
 
 use nix::{sys::signal, unistd::Pid};
 
+/// Usage: resources-kill SIGNAL PID...
+///
+/// Sends the signal to all given PIDs so that elevated actions on several
+/// processes only need one authentication. Exits with the errno of the first
+/// failed kill(2), preferring any other error over ESRCH since processes may
+/// end in the meantime.
 fn main() {
-    if let Some(arg) = env::args().nth(1) {
-        if let Some(pid) = env::args().nth(2).and_then(|s| s.trim().parse().ok()) {
+    let mut args = env::args().skip(1);
+    if let Some(arg) = args.next() {
+        let pids = args
+            .map(|s| s.trim().parse().ok())
+            .collect::<Option<Vec<i32>>>();
+        if let Some(pids) = pids.filter(|pids| !pids.is_empty()) {
             let signal = match arg.as_str() {
                 "STOP" => signal::Signal::SIGSTOP,
                 "CONT" => signal::Signal::SIGCONT,
                 "TERM" => signal::Signal::SIGTERM,
                 "KILL" => signal::Signal::SIGKILL,
-                _ => std::process::exit(254),
+                // any other signal by its full name, e.g. "SIGHUP"
+                other => match other.parse::<signal::Signal>() {
+                    Ok(signal) => signal,
+                    Err(_) => std::process::exit(254),
+                },
             };
-            let result = signal::kill(Pid::from_raw(pid), Some(signal));
-            if let Err(err) = result {
-                match err {
-                    nix::errno::Errno::UnknownErrno => std::process::exit(253),
-                    _ => std::process::exit(err as i32),
+            let mut exit_code = 0;
+            for pid in pids {
+                let code = match signal::kill(Pid::from_raw(pid), Some(signal)) {
+                    Ok(()) => continue,
+                    Err(nix::errno::Errno::UnknownErrno) => 253,
+                    Err(err) => err as i32,
                 };
+                if exit_code == 0 || exit_code == nix::errno::Errno::ESRCH as i32 {
+                    exit_code = code;
+                }
             }
-            std::process::exit(0);
+            std::process::exit(exit_code);
         }
     }
     std::process::exit(255);
@@ -1,27 +1,20 @@
-use anyhow::{Context, Result};
-use glob::glob;
-use process_data::ProcessData;
-use tokio::task::JoinSet;
+use std::io::Write;
+
+use anyhow::Result;
+use process_data::{ProcessData, OPEN_FDS_ARG};
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    let mut tasks = JoinSet::new();
-
-    for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-        tasks.spawn(async move { ProcessData::try_from_path(entry).await });
-    }
-
-    let mut process_data = vec![];
-    while let Some(task) = tasks.join_next().await {
-        if let Ok(data) = task.unwrap() {
-            process_data.push(data);
-        }
-    }
+    let count_open_fds = std::env::args().any(|arg| arg == OPEN_FDS_ARG);
+    let all_process_data = ProcessData::all(count_open_fds).await?;
 
     let stdout = std::io::stdout();
     let mut handle = stdout.lock();
 
-    rmp_serde::encode::write(&mut handle, &*process_data).unwrap();
+    rmp_serde::encode::write(&mut handle, &all_process_data).unwrap();
+    handle.flush()?;
 
-    Ok(())
+    // reads that ran into their timeout may still be blocking the runtime's
+    // threads, exit right away instead of waiting for them on shutdown
+    std::process::exit(0)
 }
@@ -1,15 +1,18 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib;
+use gtk::glib::{self, clone};
 use process_data::Containerization;
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
 use crate::ui::window::MainWindow;
 use crate::utils::app::AppItem;
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::process::{Process, ProcessItem};
+use crate::utils::units::{convert_cpu_usage, convert_speed, convert_storage};
 
 mod imp {
 
+    use std::{cell::RefCell, collections::HashMap};
+
     use super::*;
 
     use gtk::CompositeTemplate;
@@ -28,6 +31,8 @@ mod imp {
         #[template_child]
         pub memory_usage: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub swap_usage: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub drive_read_speed: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub drive_read_total: TemplateChild<adw::ActionRow>,
@@ -36,11 +41,24 @@ mod imp {
         #[template_child]
         pub drive_write_total: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub page_faults: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub open_fds: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub id: TemplateChild<adw::ActionRow>,
         #[template_child]
-        pub processes_amount: TemplateChild<adw::ActionRow>,
+        pub processes_amount: TemplateChild<adw::ExpanderRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub connections: TemplateChild<adw::ExpanderRow>,
+        pub remote_addresses: RefCell<Option<Vec<String>>>,
+        pub connection_rows: RefCell<Vec<adw::ActionRow>>,
+        pub process_rows: RefCell<HashMap<i32, adw::ActionRow>>,
+        #[template_child]
+        pub copy_summary_button: TemplateChild<gtk::Button>,
+        /// The app as of the last update, used for copying its summary
+        pub app: RefCell<Option<AppItem>>,
     }
 
     #[glib::object_subclass]
@@ -89,15 +107,29 @@ impl ResAppDialog {
     pub fn init(&self, app: &AppItem) {
         self.set_transient_for(Some(&MainWindow::default()));
         self.setup_widgets(app);
+        self.setup_signals();
     }
 
     pub fn setup_widgets(&self, app: &AppItem) {
         self.update(app);
     }
 
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.copy_summary_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                if let Some(app) = &*this.imp().app.borrow() {
+                    this.clipboard().set_text(&summary(app));
+                }
+            }));
+    }
+
     pub fn update(&self, app: &AppItem) {
         let imp = self.imp();
 
+        *imp.app.borrow_mut() = Some(app.clone());
+
         imp.icon.set_gicon(Some(&app.icon));
 
         imp.name.set_label(&app.display_name);
@@ -115,11 +147,14 @@ impl ResAppDialog {
         }
 
         imp.cpu_usage
-            .set_subtitle(&format!("{:.1} %", app.cpu_time_ratio * 100.0));
+            .set_subtitle(&convert_cpu_usage(app.cpu_time_ratio));
 
         imp.memory_usage
             .set_subtitle(&convert_storage(app.memory_usage as f64, false));
 
+        imp.swap_usage
+            .set_subtitle(&convert_storage(app.swap_usage as f64, false));
+
         imp.drive_read_speed
             .set_subtitle(&convert_speed(app.read_speed, false));
 
@@ -132,13 +167,119 @@ impl ResAppDialog {
         imp.drive_write_total
             .set_subtitle(&convert_storage(app.write_total as f64, false));
 
+        imp.page_faults.set_subtitle(&i18n_f(
+            "{} minor · {} major",
+            &[&app.minor_faults.to_string(), &app.major_faults.to_string()],
+        ));
+
         imp.processes_amount
             .set_subtitle(&app.processes_amount.to_string());
+        imp.processes_amount
+            .set_enable_expansion(app.processes_amount > 0);
 
         let containerized = match app.containerization {
             Containerization::None => i18n("No"),
             Containerization::Flatpak => i18n("Yes (Flatpak)"),
         };
         imp.containerized.set_subtitle(&containerized);
+
+        imp.open_fds.set_subtitle(
+            &app.open_fds
+                .map_or_else(|| "—".to_string(), |open_fds| open_fds.to_string()),
+        );
+    }
+
+    /// Shows the `remote_addresses` the app is connected to, these are
+    /// gathered in the refresh task while the dialog is open because reading
+    /// every socket of the app is rather expensive
+    pub fn update_remote_addresses(&self, remote_addresses: Vec<String>) {
+        let imp = self.imp();
+
+        if imp.remote_addresses.borrow().as_ref() != Some(&remote_addresses) {
+            let mut connection_rows = imp.connection_rows.borrow_mut();
+            for row in connection_rows.drain(..) {
+                imp.connections.remove(&row);
+            }
+            for address in &remote_addresses {
+                let row = adw::ActionRow::builder().title(address).build();
+                row.set_title_selectable(true);
+                imp.connections.add_row(&row);
+                connection_rows.push(row);
+            }
+            imp.connections
+                .set_subtitle(&remote_addresses.len().to_string());
+            imp.connections
+                .set_enable_expansion(!remote_addresses.is_empty());
+            *imp.remote_addresses.borrow_mut() = Some(remote_addresses);
+        }
+    }
+
+    /// Shows the individual `processes` of the app, only gathered while the
+    /// dialog is open
+    pub fn update_processes(&self, processes: &[ProcessItem]) {
+        let imp = self.imp();
+        let mut process_rows = imp.process_rows.borrow_mut();
+
+        process_rows.retain(|pid, row| {
+            let running = processes.iter().any(|process| process.pid == *pid);
+            if !running {
+                imp.processes_amount.remove(row);
+            }
+            running
+        });
+
+        for process in processes {
+            let row = process_rows.entry(process.pid).or_insert_with(|| {
+                // titles are markup, process names can contain e.g. "&"
+                let row = adw::ActionRow::builder()
+                    .title(glib::markup_escape_text(&process.display_name))
+                    .build();
+                row.set_subtitle_selectable(true);
+                imp.processes_amount.add_row(&row);
+                row
+            });
+            // kernel threads have no commandline, ps shows them in brackets
+            let invocation = if process.cmdline.is_empty() {
+                format!("[{}]", process.display_name)
+            } else {
+                Process::format_cmdline_args(&process.cmdline)
+            };
+            row.set_subtitle(&format!(
+                "{}\n{}",
+                i18n_f(
+                    "PID {} · {} · {}",
+                    &[
+                        &process.pid.to_string(),
+                        &convert_cpu_usage(process.cpu_time_ratio),
+                        &convert_storage(process.memory_usage as f64, false),
+                    ],
+                ),
+                glib::markup_escape_text(&invocation),
+            ));
+        }
     }
 }
+
+/// Returns a plain text summary of `app` that can be pasted e.g. into bug
+/// reports
+fn summary(app: &AppItem) -> String {
+    let pids = app
+        .pids
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    [
+        app.display_name.clone(),
+        i18n_f("ID: {}", &[app.id.as_deref().unwrap_or("—")]),
+        i18n_f(
+            "Memory: {}",
+            &[&convert_storage(app.memory_usage as f64, false)],
+        ),
+        i18n_f("Processor: {}", &[&convert_cpu_usage(app.cpu_time_ratio)]),
+        i18n_f("Processes: {}", &[&app.processes_amount.to_string()]),
+        i18n_f("Process IDs: {}", &[&pids]),
+    ]
+    .join("\n")
+}
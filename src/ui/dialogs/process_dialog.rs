@@ -1,12 +1,14 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib;
-use process_data::Containerization;
+use gtk::glib::{self, clone};
+use process_data::{CgroupKind, Containerization};
 
 use crate::config::PROFILE;
-use crate::i18n::i18n;
+use crate::i18n::{i18n, i18n_f};
 use crate::ui::window::MainWindow;
-use crate::utils::process::ProcessItem;
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::process::{
+    capability_names, dangerous_capabilities, has_all_capabilities, ProcessItem,
+};
+use crate::utils::units::{convert_cpu_usage, convert_speed, convert_storage};
 
 mod imp {
 
@@ -32,12 +34,22 @@ mod imp {
         #[template_child]
         pub drive_write_total: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub page_faults: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub pid: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub copy_pid_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub commandline: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub copy_commandline_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub user: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub wchan: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub capabilities: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub cgroup: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub containerized: TemplateChild<adw::ActionRow>,
@@ -89,6 +101,7 @@ impl ResProcessDialog {
     pub fn init<S: AsRef<str>>(&self, process: &ProcessItem, user: S) {
         self.set_transient_for(Some(&MainWindow::default()));
         self.setup_widgets(process, user.as_ref());
+        self.setup_signals();
     }
 
     pub fn setup_widgets(&self, process: &ProcessItem, user: &str) {
@@ -99,13 +112,29 @@ impl ResProcessDialog {
         self.update(process);
     }
 
+    pub fn setup_signals(&self) {
+        let imp = self.imp();
+
+        imp.copy_pid_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                let pid = this.imp().pid.subtitle().unwrap_or_default();
+                this.clipboard().set_text(&pid);
+            }));
+
+        imp.copy_commandline_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                let commandline = this.imp().commandline.subtitle().unwrap_or_default();
+                this.clipboard().set_text(&commandline);
+            }));
+    }
+
     pub fn update(&self, process: &ProcessItem) {
         let imp = self.imp();
 
         imp.name.set_label(&process.display_name);
 
         imp.cpu_usage
-            .set_subtitle(&format!("{:.1} %", process.cpu_time_ratio * 100.0));
+            .set_subtitle(&convert_cpu_usage(process.cpu_time_ratio));
 
         imp.memory_usage
             .set_subtitle(&convert_storage(process.memory_usage as f64, false));
@@ -138,19 +167,61 @@ impl ResProcessDialog {
             imp.drive_write_total.set_subtitle(&i18n("N/A"));
         }
 
+        imp.page_faults.set_subtitle(&i18n_f(
+            "{} minor · {} major",
+            &[
+                &process.minor_faults.to_string(),
+                &process.major_faults.to_string(),
+            ],
+        ));
+
         imp.pid.set_subtitle(&process.pid.to_string());
 
         imp.commandline.set_subtitle(&process.commandline);
         imp.commandline.set_tooltip_text(Some(&process.commandline));
 
-        imp.cgroup
-            .set_subtitle(&process.cgroup.clone().unwrap_or_else(|| i18n("N/A")));
-        imp.cgroup
-            .set_tooltip_text(Some(&process.cgroup.clone().unwrap_or_else(|| i18n("N/A"))));
+        if let Some(wchan) = &process.wchan {
+            imp.wchan.set_subtitle(wchan);
+            imp.wchan.set_visible(true);
+        } else {
+            imp.wchan.set_visible(false);
+        }
 
-        let containerized = match process.containerization {
-            Containerization::None => i18n("No"),
-            Containerization::Flatpak => i18n("Yes (Flatpak)"),
+        let capabilities = if has_all_capabilities(process.effective_capabilities) {
+            i18n("All")
+        } else if process.effective_capabilities == 0 {
+            i18n("None")
+        } else {
+            capability_names(process.effective_capabilities).join(", ")
+        };
+        imp.capabilities.set_subtitle(&capabilities);
+        // capabilities that are enough to take over the system deserve a warning
+        let dangerous = dangerous_capabilities(process.effective_capabilities);
+        if dangerous.is_empty() {
+            imp.capabilities.remove_css_class("warning");
+            imp.capabilities.set_tooltip_text(Some(&capabilities));
+        } else {
+            imp.capabilities.add_css_class("warning");
+            imp.capabilities.set_tooltip_text(Some(&i18n_f(
+                "Can gain full control over the system through {}",
+                &[&dangerous.join(", ")],
+            )));
+        }
+
+        let cgroup = match (&process.cgroup, process.cgroup_kind_name()) {
+            (Some(cgroup), Some(kind)) => i18n_f("{} ({})", &[cgroup, &kind]),
+            (Some(cgroup), None) => cgroup.clone(),
+            (None, Some(kind)) => kind,
+            (None, None) => i18n("N/A"),
+        };
+        imp.cgroup.set_subtitle(&cgroup);
+        imp.cgroup.set_tooltip_text(Some(&cgroup));
+
+        let containerized = match (&process.containerization, process.cgroup_kind) {
+            (Containerization::Flatpak, _) => i18n("Yes (Flatpak)"),
+            (Containerization::None, CgroupKind::Podman) => i18n("Yes (Podman)"),
+            (Containerization::None, CgroupKind::Docker) => i18n("Yes (Docker)"),
+            (Containerization::None, _) => i18n("No"),
         };
         imp.containerized.set_subtitle(&containerized);
     }
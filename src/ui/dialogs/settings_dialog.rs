@@ -1,9 +1,9 @@
 use adw::{prelude::*, subclass::prelude::*};
-use gtk::glib;
+use gtk::glib::{self, clone};
 
 use crate::{
     config::PROFILE,
-    utils::settings::{Base, RefreshSpeed, TemperatureUnit, SETTINGS},
+    utils::settings::{Base, GroupingMode, RefreshSpeed, TemperatureUnit, SETTINGS},
 };
 
 mod imp {
@@ -20,7 +20,11 @@ mod imp {
         #[template_child]
         pub network_bits_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
+        pub memory_show_available_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
         pub temperature_combo_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub cpu_precision_row: TemplateChild<adw::SpinRow>,
 
         #[template_child]
         pub refresh_speed_combo_row: TemplateChild<adw::ComboRow>,
@@ -28,10 +32,22 @@ mod imp {
         pub show_search_on_start_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub sidebar_details_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub memory_graph_auto_scale_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub smooth_graphs_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub color_code_trust_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub confirm_process_actions_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub always_confirm_destructive_actions_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub apps_show_memory_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
+        pub apps_show_swap_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
         pub apps_show_cpu_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub apps_show_drive_read_speed_row: TemplateChild<adw::SwitchRow>,
@@ -41,6 +57,28 @@ mod imp {
         pub apps_show_drive_write_speed_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub apps_show_drive_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_running_since_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_priority_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_open_fds_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_state_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_show_net_speed_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_pin_system_processes_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_grouping_combo_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub apps_hide_idle_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub apps_idle_cpu_threshold_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub apps_idle_memory_threshold_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
+        pub end_grace_period_row: TemplateChild<adw::SpinRow>,
 
         #[template_child]
         pub processes_show_id_row: TemplateChild<adw::SwitchRow>,
@@ -58,10 +96,14 @@ mod imp {
         pub processes_show_drive_write_speed_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
         pub processes_show_drive_write_total_row: TemplateChild<adw::SwitchRow>,
+        #[template_child]
+        pub processes_show_cgroup_row: TemplateChild<adw::SwitchRow>,
 
         #[template_child]
         pub show_virtual_drives_row: TemplateChild<adw::SwitchRow>,
         #[template_child]
+        pub drive_full_threshold_row: TemplateChild<adw::SpinRow>,
+        #[template_child]
         pub show_virtual_network_interfaces_row: TemplateChild<adw::SwitchRow>,
     }
 
@@ -122,8 +164,12 @@ impl ResSettingsDialog {
         imp.prefix_combo_row
             .set_selected((SETTINGS.base() as u8) as u32);
         imp.network_bits_row.set_active(SETTINGS.network_bits());
+        imp.memory_show_available_row
+            .set_active(SETTINGS.memory_show_available());
         imp.temperature_combo_row
             .set_selected((SETTINGS.temperature_unit() as u8) as u32);
+        imp.cpu_precision_row
+            .set_value(SETTINGS.cpu_precision() as f64);
 
         imp.refresh_speed_combo_row
             .set_selected((SETTINGS.refresh_speed() as u8) as u32);
@@ -131,9 +177,21 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.sidebar_details());
         imp.show_search_on_start_row
             .set_active(SETTINGS.show_search_on_start());
+        imp.memory_graph_auto_scale_row
+            .set_active(SETTINGS.memory_graph_auto_scale());
+        imp.smooth_graphs_row.set_active(SETTINGS.smooth_graphs());
+        imp.color_code_trust_row
+            .set_active(SETTINGS.color_code_trust());
+        imp.confirm_process_actions_row
+            .set_active(SETTINGS.confirm_process_actions());
+        imp.always_confirm_destructive_actions_row
+            .set_active(SETTINGS.always_confirm_destructive_actions());
+        imp.always_confirm_destructive_actions_row
+            .set_sensitive(!SETTINGS.confirm_process_actions());
 
         imp.apps_show_memory_row
             .set_active(SETTINGS.apps_show_memory());
+        imp.apps_show_swap_row.set_active(SETTINGS.apps_show_swap());
         imp.apps_show_cpu_row.set_active(SETTINGS.apps_show_cpu());
         imp.apps_show_drive_read_speed_row
             .set_active(SETTINGS.apps_show_drive_read_speed());
@@ -143,6 +201,27 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.apps_show_drive_write_speed());
         imp.apps_show_drive_write_total_row
             .set_active(SETTINGS.apps_show_drive_write_total());
+        imp.apps_show_running_since_row
+            .set_active(SETTINGS.apps_show_running_since());
+        imp.apps_show_priority_row
+            .set_active(SETTINGS.apps_show_priority());
+        imp.apps_show_open_fds_row
+            .set_active(SETTINGS.apps_show_open_fds());
+        imp.apps_show_state_row
+            .set_active(SETTINGS.apps_show_state());
+        imp.apps_show_net_speed_row
+            .set_active(SETTINGS.apps_show_net_speed());
+        imp.apps_pin_system_processes_row
+            .set_active(SETTINGS.apps_pin_system_processes());
+        imp.apps_grouping_combo_row
+            .set_selected((SETTINGS.apps_grouping() as u8) as u32);
+        imp.apps_hide_idle_row.set_active(SETTINGS.apps_hide_idle());
+        imp.apps_idle_cpu_threshold_row
+            .set_value(SETTINGS.apps_idle_cpu_threshold() as f64);
+        imp.apps_idle_memory_threshold_row
+            .set_value(SETTINGS.apps_idle_memory_threshold() as f64);
+        imp.end_grace_period_row
+            .set_value(SETTINGS.end_grace_period() as f64);
 
         imp.processes_show_id_row
             .set_active(SETTINGS.processes_show_id());
@@ -160,9 +239,13 @@ impl ResSettingsDialog {
             .set_active(SETTINGS.processes_show_drive_write_speed());
         imp.processes_show_drive_write_total_row
             .set_active(SETTINGS.processes_show_drive_write_total());
+        imp.processes_show_cgroup_row
+            .set_active(SETTINGS.processes_show_cgroup());
 
         imp.show_virtual_drives_row
             .set_active(SETTINGS.show_virtual_drives());
+        imp.drive_full_threshold_row
+            .set_value(SETTINGS.drive_full_threshold() as f64);
         imp.show_virtual_network_interfaces_row
             .set_active(SETTINGS.show_virtual_network_interfaces());
     }
@@ -180,6 +263,11 @@ impl ResSettingsDialog {
             let _ = SETTINGS.set_network_bits(switch_row.is_active());
         });
 
+        imp.memory_show_available_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_memory_show_available(switch_row.is_active());
+            });
+
         imp.temperature_combo_row
             .connect_selected_item_notify(|combo_row| {
                 if let Some(temperature_unit) =
@@ -189,6 +277,10 @@ impl ResSettingsDialog {
                 }
             });
 
+        imp.cpu_precision_row.connect_value_notify(|spin_row| {
+            let _ = SETTINGS.set_cpu_precision(spin_row.value() as i32);
+        });
+
         imp.refresh_speed_combo_row
             .connect_selected_item_notify(|combo_row| {
                 if let Some(refresh_speed) = RefreshSpeed::from_repr(combo_row.selected() as u8) {
@@ -205,6 +297,35 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_show_search_on_start(switch_row.is_active());
             });
 
+        imp.memory_graph_auto_scale_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_memory_graph_auto_scale(switch_row.is_active());
+            });
+
+        imp.smooth_graphs_row.connect_active_notify(|switch_row| {
+            let _ = SETTINGS.set_smooth_graphs(switch_row.is_active());
+        });
+
+        imp.color_code_trust_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_color_code_trust(switch_row.is_active());
+            });
+
+        imp.confirm_process_actions_row.connect_active_notify(
+            clone!(@weak self as this => move |switch_row| {
+                // only matters if confirmations are disabled in general
+                this.imp()
+                    .always_confirm_destructive_actions_row
+                    .set_sensitive(!switch_row.is_active());
+                let _ = SETTINGS.set_confirm_process_actions(switch_row.is_active());
+            }),
+        );
+
+        imp.always_confirm_destructive_actions_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_always_confirm_destructive_actions(switch_row.is_active());
+            });
+
         imp.apps_show_cpu_row.connect_active_notify(|switch_row| {
             let _ = SETTINGS.set_apps_show_cpu(switch_row.is_active());
         });
@@ -214,6 +335,10 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_apps_show_memory(switch_row.is_active());
             });
 
+        imp.apps_show_swap_row.connect_active_notify(|switch_row| {
+            let _ = SETTINGS.set_apps_show_swap(switch_row.is_active());
+        });
+
         imp.apps_show_drive_read_speed_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_apps_show_drive_read_speed(switch_row.is_active());
@@ -234,6 +359,60 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_apps_show_drive_write_total(switch_row.is_active());
             });
 
+        imp.apps_show_running_since_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_running_since(switch_row.is_active());
+            });
+
+        imp.apps_show_priority_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_priority(switch_row.is_active());
+            });
+
+        imp.apps_show_open_fds_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_open_fds(switch_row.is_active());
+            });
+
+        imp.apps_show_state_row.connect_active_notify(|switch_row| {
+            let _ = SETTINGS.set_apps_show_state(switch_row.is_active());
+        });
+
+        imp.apps_show_net_speed_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_show_net_speed(switch_row.is_active());
+            });
+
+        imp.apps_pin_system_processes_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_apps_pin_system_processes(switch_row.is_active());
+            });
+
+        imp.apps_grouping_combo_row
+            .connect_selected_item_notify(|combo_row| {
+                if let Some(grouping) = GroupingMode::from_repr(combo_row.selected() as u8) {
+                    let _ = SETTINGS.set_apps_grouping(grouping);
+                }
+            });
+
+        imp.apps_hide_idle_row.connect_active_notify(|switch_row| {
+            let _ = SETTINGS.set_apps_hide_idle(switch_row.is_active());
+        });
+
+        imp.apps_idle_cpu_threshold_row
+            .connect_value_notify(|spin_row| {
+                let _ = SETTINGS.set_apps_idle_cpu_threshold(spin_row.value() as i32);
+            });
+
+        imp.apps_idle_memory_threshold_row
+            .connect_value_notify(|spin_row| {
+                let _ = SETTINGS.set_apps_idle_memory_threshold(spin_row.value() as i32);
+            });
+
+        imp.end_grace_period_row.connect_value_notify(|spin_row| {
+            let _ = SETTINGS.set_end_grace_period(spin_row.value() as i32);
+        });
+
         imp.processes_show_id_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_processes_show_id(switch_row.is_active());
@@ -274,11 +453,21 @@ impl ResSettingsDialog {
                 let _ = SETTINGS.set_processes_show_drive_write_total(switch_row.is_active());
             });
 
+        imp.processes_show_cgroup_row
+            .connect_active_notify(|switch_row| {
+                let _ = SETTINGS.set_processes_show_cgroup(switch_row.is_active());
+            });
+
         imp.show_virtual_drives_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_drives(switch_row.is_active());
             });
 
+        imp.drive_full_threshold_row
+            .connect_value_notify(|spin_row| {
+                let _ = SETTINGS.set_drive_full_threshold(spin_row.value() as i32);
+            });
+
         imp.show_virtual_network_interfaces_row
             .connect_active_notify(|switch_row| {
                 let _ = SETTINGS.set_show_virtual_network_interfaces(switch_row.is_active());
@@ -4,6 +4,7 @@ use gtk::{
 };
 
 use crate::utils::app::AppItem;
+use crate::utils::smoothing::{smoothing_alpha, Ema};
 
 mod imp {
     use std::cell::{Cell, RefCell};
@@ -38,6 +39,9 @@ mod imp {
         #[property(get, set)]
         memory_usage: Cell<u64>,
 
+        #[property(get, set)]
+        swap_usage: Cell<u64>,
+
         #[property(get, set)]
         read_speed: Cell<f64>,
 
@@ -50,7 +54,27 @@ mod imp {
         #[property(get, set)]
         write_total: Cell<u64>,
 
+        #[property(get, set)]
+        uptime: Cell<u64>,
+
+        #[property(get, set)]
+        nice: Cell<i32>,
+
+        /// -1 if the open file descriptors couldn't be counted
+        #[property(get, set)]
+        open_fds: Cell<i64>,
+
+        /// The `ProcessState` as `u8`
+        #[property(get, set)]
+        state: Cell<u8>,
+
+        /// -1 if the network throughput can't be estimated
+        #[property(get, set)]
+        net_speed: Cell<f64>,
+
         pub app_item: RefCell<Option<AppItem>>,
+
+        pub cpu_usage_ema: Cell<Ema>,
     }
 
     impl Default for ApplicationEntry {
@@ -62,11 +86,18 @@ mod imp {
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
                 cpu_usage: Cell::new(0.0),
                 memory_usage: Cell::new(0),
+                swap_usage: Cell::new(0),
                 read_speed: Cell::new(0.0),
                 read_total: Cell::new(0),
                 write_speed: Cell::new(0.0),
                 write_total: Cell::new(0),
+                uptime: Cell::new(0),
+                nice: Cell::new(0),
+                open_fds: Cell::new(-1),
+                state: Cell::new(0),
+                net_speed: Cell::new(-1.0),
                 app_item: RefCell::new(None),
+                cpu_usage_ema: Cell::default(),
             }
         }
     }
@@ -155,26 +186,54 @@ impl ApplicationEntry {
             .property("icon", &app_item.icon)
             .property("id", &app_item.id)
             .build();
-        this.set_cpu_usage(app_item.cpu_time_ratio);
+        this.set_cpu_usage(this.smoothed_cpu_usage(app_item.cpu_time_ratio));
         this.set_memory_usage(app_item.memory_usage as u64);
+        this.set_swap_usage(app_item.swap_usage as u64);
         this.set_read_speed(app_item.read_speed);
         this.set_read_total(app_item.read_total);
         this.set_write_speed(app_item.write_speed);
         this.set_write_total(app_item.write_total);
+        this.set_uptime(app_item.uptime.as_secs());
+        this.set_nice(app_item.nice);
+        this.set_open_fds(app_item.open_fds.map_or(-1, |open_fds| open_fds as i64));
+        this.set_net_speed(app_item.net_speed.unwrap_or(-1.0));
+        this.set_state(app_item.state as u8);
         this.imp().app_item.replace(Some(app_item));
         this
     }
 
     pub fn update(&self, app_item: AppItem) {
-        self.set_cpu_usage(app_item.cpu_time_ratio);
+        self.set_cpu_usage(self.smoothed_cpu_usage(app_item.cpu_time_ratio));
         self.set_memory_usage(app_item.memory_usage as u64);
+        self.set_swap_usage(app_item.swap_usage as u64);
         self.set_read_speed(app_item.read_speed);
         self.set_read_total(app_item.read_total);
         self.set_write_speed(app_item.write_speed);
         self.set_write_total(app_item.write_total);
+        self.set_uptime(app_item.uptime.as_secs());
+        self.set_nice(app_item.nice);
+        self.set_open_fds(app_item.open_fds.map_or(-1, |open_fds| open_fds as i64));
+        self.set_net_speed(app_item.net_speed.unwrap_or(-1.0));
+        self.set_state(app_item.state as u8);
         self.imp().app_item.replace(Some(app_item));
     }
 
+    fn smoothed_cpu_usage(&self, cpu_usage: f32) -> f32 {
+        let mut ema = self.imp().cpu_usage_ema.get();
+        let smoothed = ema.push(f64::from(cpu_usage), smoothing_alpha());
+        self.imp().cpu_usage_ema.set(ema);
+        smoothed as f32
+    }
+
+    /// Whether at least one of the processes is owned by the user `uid`
+    pub fn has_process_of_user(&self, uid: u32) -> bool {
+        self.imp()
+            .app_item
+            .borrow()
+            .as_ref()
+            .is_some_and(|app_item| app_item.uids.binary_search(&uid).is_ok())
+    }
+
     pub fn app_item(&self) -> Option<AppItem> {
         let imp = self.imp();
         let item = imp.app_item.take();
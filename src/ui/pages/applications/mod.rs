@@ -1,31 +1,58 @@
 mod application_entry;
 mod application_name_cell;
 
-use std::collections::HashSet;
+use std::time::Duration;
 
 use adw::ResponseAppearance;
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::glib::{self, clone, closure, Object, Sender};
-use gtk::{gio, CustomSorter, FilterChange, Ordering, SortType, Widget};
+use gtk::{gio, CustomSorter, FilterChange, Ordering, SortType, SorterChange, Widget};
 use gtk_macros::send;
+use hashbrown::{HashMap, HashSet};
 
 use log::error;
+use nix::sys::signal::Signal;
+use regex::{Regex, RegexBuilder};
 
+use crate::application::Application;
 use crate::config::PROFILE;
-use crate::i18n::{i18n, i18n_f};
+use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::dialogs::app_dialog::ResAppDialog;
+use crate::ui::pages::context_menu::{add_context_menu_gesture, new_context_menu, ContextMenuPage};
 use crate::ui::window::{self, Action, MainWindow};
-use crate::utils::app::{AppItem, AppsContext};
-use crate::utils::process::ProcessAction;
-use crate::utils::settings::SETTINGS;
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::app::{AppItem, AppItemsDiff, AppsContext};
+use crate::utils::fuzzy::fuzzy_score;
+use crate::utils::process::{ProcessAction, ProcessState};
+use crate::utils::settings::{Base, SETTINGS};
+use crate::utils::units::{
+    convert_cpu_usage, convert_duration, convert_speed, convert_storage, format_bytes,
+};
 
 use self::application_entry::ApplicationEntry;
 use self::application_name_cell::ResApplicationNameCell;
 
+/// Decimal places of amounts of bytes in the columns, the columns are narrow
+/// so they're less precise than e.g. the summary
+const COLUMN_PRECISION: usize = 1;
+
+/// Signals that can be sent using the "Send Signal…" dialog
+const SIGNALS: &[Signal] = &[
+    Signal::SIGHUP,
+    Signal::SIGINT,
+    Signal::SIGQUIT,
+    Signal::SIGUSR1,
+    Signal::SIGUSR2,
+    Signal::SIGALRM,
+    Signal::SIGTERM,
+    Signal::SIGKILL,
+    Signal::SIGSTOP,
+    Signal::SIGCONT,
+    Signal::SIGTSTP,
+];
+
 mod imp {
     use std::{
-        cell::{Cell, RefCell},
+        cell::{Cell, OnceCell, RefCell},
         sync::OnceLock,
     };
 
@@ -50,20 +77,51 @@ mod imp {
         #[template_child]
         pub search_entry: TemplateChild<gtk::SearchEntry>,
         #[template_child]
+        pub regex_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
         pub applications_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
         #[template_child]
+        pub summary: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub app_context_menu: TemplateChild<gio::MenuModel>,
+        #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
+        pub user_drop_down: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub columns_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
         pub information_button: TemplateChild<gtk::Button>,
         #[template_child]
         pub end_application_button: TemplateChild<adw::SplitButton>,
 
         pub store: RefCell<gio::ListStore>,
-        pub selection_model: RefCell<gtk::SingleSelection>,
+        pub selection_model: RefCell<gtk::MultiSelection>,
         pub filter_model: RefCell<gtk::FilterListModel>,
         pub sort_model: RefCell<gtk::SortListModel>,
         pub column_view: RefCell<gtk::ColumnView>,
+        pub columns: RefCell<Vec<(&'static str, Option<&'static str>, gtk::ColumnViewColumn)>>,
+        /// Pending `save_columns_state()`, dragging a column's edge changes
+        /// its width many times per second
+        pub save_columns_source: RefCell<Option<glib::SourceId>>,
+        pub context_menu: OnceCell<gtk::PopoverMenu>,
         pub open_dialog: RefCell<Option<(Option<String>, ResAppDialog)>>,
+        /// The compiled search if it's used as a regular expression, `None`
+        /// if that's disabled or the search is not a valid regex
+        pub search_regex: RefCell<Option<Regex>>,
+        /// The `search_score()` of every app by ID for the current search,
+        /// cleared whenever the search changes
+        pub search_scores: RefCell<HashMap<Option<glib::GString>, Option<i32>>>,
+        /// The users offered by `user_drop_down`, the first entry ("All
+        /// Users") has no ID
+        pub user_filter_uids: RefCell<Vec<Option<u32>>>,
+        /// The IDs of the apps that are always sorted to the top
+        pub pinned_apps: RefCell<HashSet<String>>,
+        /// The IDs of the apps whose memory usage is above their alert
+        /// threshold and that have been notified about already
+        pub exceeded_memory_alerts: RefCell<HashSet<String>>,
+        /// The IDs of the selected apps, `None` is System Processes
+        pub selected_ids: RefCell<HashSet<Option<String>>>,
 
         pub sender: OnceLock<Sender<Action>>,
 
@@ -106,16 +164,30 @@ mod imp {
                 toast_overlay: Default::default(),
                 search_revealer: Default::default(),
                 search_entry: Default::default(),
+                regex_button: Default::default(),
                 search_button: Default::default(),
+                user_drop_down: Default::default(),
+                columns_button: Default::default(),
                 information_button: Default::default(),
                 store: gio::ListStore::new::<ApplicationEntry>().into(),
                 selection_model: Default::default(),
                 filter_model: Default::default(),
                 sort_model: Default::default(),
                 column_view: Default::default(),
+                columns: Default::default(),
+                save_columns_source: Default::default(),
+                app_context_menu: Default::default(),
+                context_menu: Default::default(),
                 open_dialog: Default::default(),
+                search_regex: Default::default(),
+                search_scores: Default::default(),
+                user_filter_uids: RefCell::new(vec![None]),
+                pinned_apps: RefCell::new(SETTINGS.apps_pinned().into_iter().collect()),
+                exceeded_memory_alerts: Default::default(),
+                selected_ids: Default::default(),
                 sender: Default::default(),
                 applications_scrolled_window: Default::default(),
+                summary: Default::default(),
                 end_application_button: Default::default(),
                 uses_progress_bar: Cell::new(false),
                 icon: RefCell::new(ThemedIcon::new("app-symbolic").into()),
@@ -133,35 +205,156 @@ mod imp {
 
         fn class_init(klass: &mut Self::Class) {
             klass.install_action(
-                "applications.kill-application",
+                "applications.end-application-forcefully",
                 None,
                 move |res_applications, _, _| {
                     if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::KILL);
+                        res_applications.end_app_forcefully_dialog(app);
                     }
                 },
             );
 
+            klass.install_action(
+                "applications.kill-application",
+                None,
+                move |res_applications, _, _| {
+                    res_applications.execute_process_action_dialog_apps(
+                        res_applications.get_selected_app_items(),
+                        ProcessAction::KILL,
+                    );
+                },
+            );
+
             klass.install_action(
                 "applications.halt-application",
                 None,
+                move |res_applications, _, _| {
+                    res_applications.execute_process_action_dialog_apps(
+                        res_applications.get_selected_app_items(),
+                        ProcessAction::STOP,
+                    );
+                },
+            );
+
+            klass.install_action(
+                "applications.continue-application",
+                None,
+                move |res_applications, _, _| {
+                    res_applications.execute_process_action_dialog_apps(
+                        res_applications.get_selected_app_items(),
+                        ProcessAction::CONT,
+                    );
+                },
+            );
+
+            klass.install_action(
+                "applications.send-signal",
+                None,
                 move |res_applications, _, _| {
                     if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::STOP);
+                        res_applications.send_signal_dialog(app);
                     }
                 },
             );
 
             klass.install_action(
-                "applications.continue-application",
+                "applications.end-visible-applications",
+                None,
+                move |res_applications, _, _| {
+                    res_applications.end_visible_apps_dialog();
+                },
+            );
+
+            klass.install_action(
+                "applications.change-priority",
+                None,
+                move |res_applications, _, _| {
+                    if let Some(app) = res_applications.get_selected_app_item() {
+                        res_applications.change_priority_dialog(app);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "applications.pin-application",
+                None,
+                move |res_applications, _, _| {
+                    res_applications.set_selected_apps_pinned(true);
+                },
+            );
+
+            klass.install_action(
+                "applications.unpin-application",
+                None,
+                move |res_applications, _, _| {
+                    res_applications.set_selected_apps_pinned(false);
+                },
+            );
+
+            klass.install_action(
+                "applications.set-memory-alert",
+                None,
+                move |res_applications, _, _| {
+                    if let Some(app) = res_applications.get_selected_app_item() {
+                        res_applications.memory_alert_dialog(app);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "applications.remove-memory-alert",
+                None,
+                move |res_applications, _, _| {
+                    if let Some(id) = res_applications
+                        .get_selected_app_item()
+                        .and_then(|app| app.id)
+                    {
+                        res_applications.set_memory_alert(&id, None);
+                    }
+                },
+            );
+
+            klass.install_action(
+                "applications.open-working-directory",
                 None,
                 move |res_applications, _, _| {
                     if let Some(app) = res_applications.get_selected_app_item() {
-                        res_applications.execute_process_action_dialog(app, ProcessAction::CONT);
+                        res_applications.open_working_directory(&app);
                     }
                 },
             );
 
+            klass.install_action(
+                "applications.copy-pids",
+                None,
+                move |res_applications, _, _| {
+                    let pids = res_applications
+                        .get_selected_app_items()
+                        .into_iter()
+                        .flat_map(|app| app.pids)
+                        .collect::<Vec<_>>();
+                    if pids.is_empty() {
+                        return;
+                    }
+                    res_applications.clipboard().set_text(
+                        &pids
+                            .iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    );
+                    res_applications
+                        .imp()
+                        .toast_overlay
+                        .add_toast(adw::Toast::new(&ni18n_f(
+                            "Copied {} PID",
+                            "Copied {} PIDs",
+                            pids.len() as u32,
+                            &[&pids.len().to_string()],
+                        )));
+                },
+            );
+
             Self::bind_template(klass);
         }
 
@@ -226,23 +419,74 @@ impl ResApplications {
     pub fn setup_widgets(&self) {
         let imp = self.imp();
 
-        let column_view = gtk::ColumnView::new(None::<gtk::SingleSelection>);
+        let column_view = gtk::ColumnView::new(None::<gtk::MultiSelection>);
         let store = gio::ListStore::new::<ApplicationEntry>();
         let filter_model = gtk::FilterListModel::new(
             Some(store.clone()),
             Some(gtk::CustomFilter::new(
-                clone!(@strong self as this => move |obj| this.search_filter(obj)),
+                clone!(@strong self as this => move |obj| this.list_filter(obj)),
             )),
         );
-        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), column_view.sorter());
-        let selection_model = gtk::SingleSelection::new(Some(sort_model.clone()));
+        // Pinned apps always come first, regardless of the column's sort order
+        let pinned_sorter = CustomSorter::new(
+            clone!(@weak self as this => @default-return Ordering::Equal, move |a, b| {
+                let pinned_apps = this.imp().pinned_apps.borrow();
+                let is_pinned = |obj: &glib::Object| {
+                    obj.downcast_ref::<ApplicationEntry>()
+                        .unwrap()
+                        .id()
+                        .is_some_and(|id| pinned_apps.contains(id.as_str()))
+                };
+                is_pinned(b).cmp(&is_pinned(a)).into()
+            }),
+        );
+        // System Processes may be pinned to the bottom, regardless of the column's sort order
+        let system_processes_sorter = CustomSorter::new(move |a, b| {
+            if !SETTINGS.apps_pin_system_processes() {
+                return Ordering::Equal;
+            }
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().id().is_none();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().id().is_none();
+            item_a.cmp(&item_b).into()
+        });
+        // Better search matches come first while searching
+        let search_score_sorter = CustomSorter::new(
+            clone!(@weak self as this => @default-return Ordering::Equal, move |a, b| {
+                if !this.imp().search_revealer.reveals_child() {
+                    return Ordering::Equal;
+                }
+                let score_a = this.search_score(a.downcast_ref::<ApplicationEntry>().unwrap());
+                let score_b = this.search_score(b.downcast_ref::<ApplicationEntry>().unwrap());
+                score_b.cmp(&score_a).into()
+            }),
+        );
+        let sorter = gtk::MultiSorter::new();
+        sorter.append(pinned_sorter.clone());
+        sorter.append(system_processes_sorter.clone());
+        sorter.append(search_score_sorter);
+        if let Some(column_view_sorter) = column_view.sorter() {
+            sorter.append(column_view_sorter);
+        }
+        SETTINGS.connect_apps_pin_system_processes(move |_| {
+            system_processes_sorter.changed(SorterChange::Different);
+        });
+        SETTINGS.connect_apps_pinned(clone!(@weak self as this => move |pinned| {
+            *this.imp().pinned_apps.borrow_mut() = pinned.into_iter().collect();
+            pinned_sorter.changed(SorterChange::Different);
+            this.update_pin_actions(&this.get_selected_app_items());
+        }));
+        let sort_model = gtk::SortListModel::new(Some(filter_model.clone()), Some(sorter));
+        // Ctrl and Shift clicks select several apps to apply actions to
+        let selection_model = gtk::MultiSelection::new(Some(sort_model.clone()));
         column_view.set_model(Some(&selection_model));
-        selection_model.set_can_unselect(true);
-        selection_model.set_autoselect(false);
 
         *imp.store.borrow_mut() = store;
         *imp.selection_model.borrow_mut() = selection_model;
         *imp.sort_model.borrow_mut() = sort_model;
+        filter_model.connect_items_changed(clone!(@weak self as this => move |_, _, _, _| {
+            this.update_summary();
+        }));
+
         *imp.filter_model.borrow_mut() = filter_model;
 
         let name_col_factory = gtk::SignalListItemFactory::new();
@@ -254,6 +498,7 @@ impl ResApplications {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = ResApplicationNameCell::new();
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("name")
                 .bind(&row, "name", Widget::NONE);
@@ -261,6 +506,21 @@ impl ResApplications {
                 .chain_property::<ApplicationEntry>("icon")
                 .bind(&row, "icon", Widget::NONE);
         });
+        name_col_factory.connect_bind(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = item
+                .child()
+                .and_downcast::<ResApplicationNameCell>()
+                .unwrap();
+            if let Some(app_item) = item
+                .item()
+                .and_downcast::<ApplicationEntry>()
+                .unwrap()
+                .app_item()
+            {
+                row.set_trust_level(app_item.trust_level());
+            }
+        });
         let name_col_sorter = CustomSorter::new(move |a, b| {
             let item_a = a.downcast_ref::<ApplicationEntry>().unwrap();
             let item_b = b.downcast_ref::<ApplicationEntry>().unwrap();
@@ -279,12 +539,29 @@ impl ResApplications {
         memory_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Memory"));
             row.set_min_chars(9);
+            // Break the usage down, a large number might be mostly shared libraries
+            let list_item = item.downgrade();
+            row.set_has_tooltip(true);
+            row.connect_query_tooltip(move |_, _, _, _, tooltip| {
+                let Some(app) = list_item
+                    .upgrade()
+                    .and_then(|item| item.item())
+                    .and_downcast::<ApplicationEntry>()
+                    .and_then(|entry| entry.app_item())
+                else {
+                    return false;
+                };
+                tooltip.set_text(Some(&memory_breakdown(&app)));
+                true
+            });
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("memory_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
-                    convert_storage(memory_usage as f64, false)
+                    format_bytes(memory_usage as f64, SETTINGS.base(), COLUMN_PRECISION)
                 }))
                 .bind(&row, "text", Widget::NONE);
         });
@@ -294,7 +571,31 @@ impl ResApplications {
             item_a.cmp(&item_b).into()
         });
         memory_col.set_sorter(Some(&memory_col_sorter));
-        memory_col.set_visible(SETTINGS.apps_show_memory());
+
+        let swap_col_factory = gtk::SignalListItemFactory::new();
+        let swap_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Swap")), Some(swap_col_factory.clone()));
+        swap_col.set_resizable(true);
+        swap_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Swap"));
+            row.set_min_chars(9);
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("swap_usage")
+                .chain_closure::<String>(closure!(|_: Option<Object>, swap_usage: u64| {
+                    format_bytes(swap_usage as f64, SETTINGS.base(), COLUMN_PRECISION)
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let swap_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().swap_usage();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().swap_usage();
+            item_a.cmp(&item_b).into()
+        });
+        swap_col.set_sorter(Some(&swap_col_sorter));
 
         let cpu_col_factory = gtk::SignalListItemFactory::new();
         let cpu_col =
@@ -303,11 +604,13 @@ impl ResApplications {
         cpu_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Processor"));
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("cpu_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
-                    format!("{:.1} %", cpu_usage * 100.0)
+                    convert_cpu_usage(cpu_usage)
                 }))
                 .bind(&row, "text", Widget::NONE);
         });
@@ -323,7 +626,6 @@ impl ResApplications {
             }
         });
         cpu_col.set_sorter(Some(&cpu_col_sorter));
-        cpu_col.set_visible(SETTINGS.apps_show_cpu());
 
         let read_speed_col_factory = gtk::SignalListItemFactory::new();
         let read_speed_col = gtk::ColumnViewColumn::new(
@@ -334,7 +636,9 @@ impl ResApplications {
         read_speed_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Drive Read"));
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("read_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
@@ -354,7 +658,6 @@ impl ResApplications {
             }
         });
         read_speed_col.set_sorter(Some(&read_speed_col_sorter));
-        read_speed_col.set_visible(SETTINGS.apps_show_drive_read_speed());
 
         let read_total_col_factory = gtk::SignalListItemFactory::new();
         let read_total_col = gtk::ColumnViewColumn::new(
@@ -365,11 +668,13 @@ impl ResApplications {
         read_total_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Drive Read Total"));
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("read_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_total: u64| {
-                    convert_storage(read_total as f64, false)
+                    format_bytes(read_total as f64, SETTINGS.base(), COLUMN_PRECISION)
                 }))
                 .bind(&row, "text", Widget::NONE);
         });
@@ -379,7 +684,6 @@ impl ResApplications {
             item_a.cmp(&item_b).into()
         });
         read_total_col.set_sorter(Some(&read_total_col_sorter));
-        read_total_col.set_visible(SETTINGS.apps_show_drive_read_total());
 
         let write_speed_col_factory = gtk::SignalListItemFactory::new();
         let write_speed_col = gtk::ColumnViewColumn::new(
@@ -390,7 +694,9 @@ impl ResApplications {
         write_speed_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Drive Write"));
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("write_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
@@ -410,7 +716,6 @@ impl ResApplications {
             }
         });
         write_speed_col.set_sorter(Some(&write_speed_col_sorter));
-        write_speed_col.set_visible(SETTINGS.apps_show_drive_write_speed());
 
         let write_total_col_factory = gtk::SignalListItemFactory::new();
         let write_total_col = gtk::ColumnViewColumn::new(
@@ -421,11 +726,13 @@ impl ResApplications {
         write_total_col_factory.connect_setup(move |_factory, item| {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Drive Write Total"));
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
             item.property_expression("item")
                 .chain_property::<ApplicationEntry>("write_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_total: u64| {
-                    convert_storage(write_total as f64, false)
+                    format_bytes(write_total as f64, SETTINGS.base(), COLUMN_PRECISION)
                 }))
                 .bind(&row, "text", Widget::NONE);
         });
@@ -435,31 +742,277 @@ impl ResApplications {
             item_a.cmp(&item_b).into()
         });
         write_total_col.set_sorter(Some(&write_total_col_sorter));
-        write_total_col.set_visible(SETTINGS.apps_show_drive_write_total());
+
+        let running_since_col_factory = gtk::SignalListItemFactory::new();
+        let running_since_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Running For")),
+            Some(running_since_col_factory.clone()),
+        );
+        running_since_col.set_resizable(true);
+        running_since_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Running For"));
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("uptime")
+                .chain_closure::<String>(closure!(|_: Option<Object>, uptime: u64| {
+                    convert_duration(Duration::from_secs(uptime))
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let running_since_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().uptime();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().uptime();
+            item_a.cmp(&item_b).into()
+        });
+        running_since_col.set_sorter(Some(&running_since_col_sorter));
+
+        let priority_col_factory = gtk::SignalListItemFactory::new();
+        let priority_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Priority")), Some(priority_col_factory.clone()));
+        priority_col.set_resizable(true);
+        priority_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Priority"));
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("nice")
+                .chain_closure::<String>(closure!(|_: Option<Object>, nice: i32| {
+                    nice.to_string()
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let priority_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().nice();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().nice();
+            item_a.cmp(&item_b).into()
+        });
+        priority_col.set_sorter(Some(&priority_col_sorter));
+
+        let open_fds_col_factory = gtk::SignalListItemFactory::new();
+        let open_fds_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Open Files")),
+            Some(open_fds_col_factory.clone()),
+        );
+        open_fds_col.set_resizable(true);
+        open_fds_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Open Files"));
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("open_fds")
+                .chain_closure::<String>(closure!(|_: Option<Object>, open_fds: i64| {
+                    if open_fds < 0 {
+                        "—".to_string()
+                    } else {
+                        open_fds.to_string()
+                    }
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let open_fds_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().open_fds();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().open_fds();
+            item_a.cmp(&item_b).into()
+        });
+        open_fds_col.set_sorter(Some(&open_fds_col_sorter));
+
+        let net_speed_col_factory = gtk::SignalListItemFactory::new();
+        let net_speed_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("Network")), Some(net_speed_col_factory.clone()));
+        net_speed_col.set_resizable(true);
+        net_speed_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("Network"));
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ApplicationEntry>("net_speed")
+                .chain_closure::<String>(closure!(|_: Option<Object>, net_speed: f64| {
+                    if net_speed < 0.0 {
+                        "—".to_string()
+                    } else {
+                        convert_speed(net_speed, true)
+                    }
+                }))
+                .bind(&row, "text", Widget::NONE);
+        });
+        let net_speed_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().net_speed();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().net_speed();
+            if item_a > item_b {
+                Ordering::Larger
+            } else if item_a < item_b {
+                Ordering::Smaller
+            } else {
+                Ordering::Equal
+            }
+        });
+        net_speed_col.set_sorter(Some(&net_speed_col_sorter));
+
+        let state_col_factory = gtk::SignalListItemFactory::new();
+        let state_col =
+            gtk::ColumnViewColumn::new(Some(&i18n("State")), Some(state_col_factory.clone()));
+        state_col.set_resizable(true);
+        state_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            label_cell_for_screen_readers(&row, i18n("State"));
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResApplications>(item, &row);
+            let state_expression = item
+                .property_expression("item")
+                .chain_property::<ApplicationEntry>("state");
+            state_expression
+                .chain_closure::<String>(closure!(|_: Option<Object>, state: u8| {
+                    ProcessState::from_repr(state).unwrap_or_default().name()
+                }))
+                .bind(&row, "text", Widget::NONE);
+            // zombies and processes stuck in uninterruptible sleep are worth a
+            // closer look
+            state_expression
+                .chain_closure::<Vec<String>>(closure!(|_: Option<Object>, state: u8| {
+                    match ProcessState::from_repr(state) {
+                        Some(ProcessState::Zombie) => vec!["error".to_string()],
+                        Some(ProcessState::UninterruptibleSleep) => vec!["warning".to_string()],
+                        _ => Vec::new(),
+                    }
+                }))
+                .bind(&row, "css-classes", Widget::NONE);
+        });
+        let state_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ApplicationEntry>().unwrap().state();
+            let item_b = b.downcast_ref::<ApplicationEntry>().unwrap().state();
+            item_a.cmp(&item_b).into()
+        });
+        state_col.set_sorter(Some(&state_col_sorter));
 
         column_view.append_column(&name_col);
         column_view.append_column(&memory_col);
+        column_view.append_column(&swap_col);
         column_view.append_column(&cpu_col);
         column_view.append_column(&read_speed_col);
         column_view.append_column(&read_total_col);
         column_view.append_column(&write_speed_col);
         column_view.append_column(&write_total_col);
+        column_view.append_column(&running_since_col);
+        column_view.append_column(&priority_col);
+        column_view.append_column(&open_fds_col);
+        column_view.append_column(&net_speed_col);
+        column_view.append_column(&state_col);
+
+        // The ID of each column is used for saving its state, the settings key
+        // decides whether it's visible. Columns with a key can be toggled in
+        // the columns menu.
+        let columns = vec![
+            ("name", None, name_col.clone()),
+            ("memory", Some("apps-show-memory"), memory_col.clone()),
+            ("swap", Some("apps-show-swap"), swap_col.clone()),
+            ("cpu", Some("apps-show-cpu"), cpu_col.clone()),
+            (
+                "read_speed",
+                Some("apps-show-drive-read-speed"),
+                read_speed_col.clone(),
+            ),
+            (
+                "read_total",
+                Some("apps-show-drive-read-total"),
+                read_total_col.clone(),
+            ),
+            (
+                "write_speed",
+                Some("apps-show-drive-write-speed"),
+                write_speed_col.clone(),
+            ),
+            (
+                "write_total",
+                Some("apps-show-drive-write-total"),
+                write_total_col.clone(),
+            ),
+            (
+                "running_since",
+                Some("apps-show-running-since"),
+                running_since_col.clone(),
+            ),
+            ("priority", Some("apps-show-priority"), priority_col.clone()),
+            ("open_fds", Some("apps-show-open-fds"), open_fds_col.clone()),
+            (
+                "net_speed",
+                Some("apps-show-net-speed"),
+                net_speed_col.clone(),
+            ),
+            ("state", Some("apps-show-state"), state_col.clone()),
+        ];
+
+        let (sort_column, ascending, widths) = SETTINGS.applications_columns();
+        let sort_column = columns
+            .iter()
+            .find(|(id, _, _)| *id == sort_column)
+            .map_or(&name_col, |(_, _, column)| column);
+        let sort_type = if ascending {
+            SortType::Ascending
+        } else {
+            SortType::Descending
+        };
+        column_view.sort_by_column(Some(sort_column), sort_type);
+
+        let column_actions = gio::SimpleActionGroup::new();
+        let columns_menu = gio::Menu::new();
+        for (id, visibility_key, column) in &columns {
+            if let Some(key) = visibility_key {
+                SETTINGS.bind(key, column, "visible").build();
+                column_actions.add_action(&SETTINGS.create_action(key));
+                columns_menu.append(column.title().as_deref(), Some(&format!("columns.{key}")));
+            }
+            if let Some(width) = widths.get(*id) {
+                column.set_fixed_width(*width);
+            }
+            column.connect_fixed_width_notify(clone!(@weak self as this => move |_| {
+                this.schedule_save_columns_state();
+            }));
+        }
 
-        column_view.sort_by_column(Some(&name_col), SortType::Ascending);
+        if let Some(column_view_sorter) = column_view.sorter() {
+            column_view_sorter.connect_changed(clone!(@weak self as this => move |_, _| {
+                this.schedule_save_columns_state();
+            }));
+        }
 
-        SETTINGS.connect_apps_show_memory(move |visible| memory_col.set_visible(visible));
-        SETTINGS.connect_apps_show_cpu(move |visible| cpu_col.set_visible(visible));
-        SETTINGS
-            .connect_apps_show_drive_read_speed(move |visible| read_speed_col.set_visible(visible));
-        SETTINGS
-            .connect_apps_show_drive_read_total(move |visible| read_total_col.set_visible(visible));
-        SETTINGS.connect_apps_show_drive_write_speed(move |visible| {
-            write_speed_col.set_visible(visible)
-        });
-        SETTINGS.connect_apps_show_drive_write_total(move |visible| {
-            write_total_col.set_visible(visible)
+        // don't lose changes that are still pending when the window closes
+        self.connect_unmap(|this| {
+            if let Some(source) = this.imp().save_columns_source.take() {
+                source.remove();
+                this.save_columns_state();
+            }
         });
 
+        self.insert_action_group("columns", Some(&column_actions));
+        imp.columns_button.set_menu_model(Some(&columns_menu));
+
+        *imp.columns.borrow_mut() = columns;
+
+        if SETTINGS.color_code_trust() {
+            column_view.add_css_class("trust-colors");
+        }
+        SETTINGS.connect_color_code_trust(clone!(@weak column_view => move |enabled| {
+            if enabled {
+                column_view.add_css_class("trust-colors");
+            } else {
+                column_view.remove_css_class("trust-colors");
+            }
+        }));
+
+        let _ = imp
+            .context_menu
+            .set(new_context_menu(&imp.app_context_menu, &column_view));
+
         column_view.set_enable_rubberband(true);
         imp.applications_scrolled_window
             .set_child(Some(&column_view));
@@ -470,19 +1023,41 @@ impl ResApplications {
         let imp = self.imp();
 
         imp.selection_model.borrow().connect_selection_changed(
-        clone!(@strong self as this => move |model, _, _| {
-            let imp = this.imp();
-                let is_system_processes = model.selected_item().map_or(false, |object| {
-                    object
-                    .downcast::<ApplicationEntry>()
-                    .unwrap()
-                    .id()
-                    .is_none()
-                });
-                imp.information_button.set_sensitive(model.selected() != u32::MAX);
-                imp.end_application_button.set_sensitive(model.selected() != u32::MAX && !is_system_processes);
+            clone!(@strong self as this => move |_, _, _| {
+                let imp = this.imp();
+                let selected = this.get_selected_app_items();
+                *imp.selected_ids.borrow_mut() =
+                    selected.iter().map(|app| app.id.clone()).collect();
+                // System Processes can't be ended, but the other selected apps can
+                let any_endable = selected
+                    .iter()
+                    .any(|app| app.is_app() && !app.is_zombie());
+                imp.information_button.set_sensitive(selected.len() == 1);
+                imp.end_application_button.set_sensitive(any_endable);
+                if !selected.is_empty() && selected.iter().all(AppItem::is_zombie) {
+                    imp.end_application_button.set_tooltip_text(Some(&i18n(
+                        "Zombie processes have already ended and only wait for their parent process to notice, so they can't be ended",
+                    )));
+                } else {
+                    imp.end_application_button.set_tooltip_text(None);
+                }
+                // these act on the processes of a single app, which System
+                // Processes doesn't have
+                let single_app = selected.len() == 1 && selected.iter().all(AppItem::is_app);
+                for action in [
+                    "applications.end-application-forcefully",
+                    "applications.send-signal",
+                    "applications.change-priority",
+                    "applications.set-memory-alert",
+                ] {
+                    this.action_set_enabled(action, single_app);
+                }
+                this.update_memory_alert_action();
+                this.update_pin_actions(&selected);
             }),
         );
+        self.update_pin_actions(&[]);
+        self.update_memory_alert_action();
 
         imp.search_button
             .connect_toggled(clone!(@strong self as this => move |button| {
@@ -491,6 +1066,9 @@ impl ResApplications {
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
+                if let Some(sorter) = imp.sort_model.borrow().sorter() {
+                    sorter.changed(SorterChange::Different);
+                }
                 if button.is_active() {
                     imp.search_entry.grab_focus();
                 }
@@ -498,23 +1076,51 @@ impl ResApplications {
 
         imp.search_entry
             .connect_search_changed(clone!(@strong self as this => move |_| {
+            this.update_search_regex();
             let imp = this.imp();
+                imp.search_scores.borrow_mut().clear();
+                if let Some(filter) = imp.filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+                if let Some(sorter) = imp.sort_model.borrow().sorter() {
+                    sorter.changed(SorterChange::Different);
+                }
+            }));
+
+        imp.user_drop_down
+            .connect_selected_notify(clone!(@strong self as this => move |_| {
+                if let Some(filter) = this.imp().filter_model.borrow().filter() {
+                    filter.changed(FilterChange::Different);
+                }
+            }));
+
+        let refilter = clone!(@weak self as this => move || {
+            if let Some(filter) = this.imp().filter_model.borrow().filter() {
+                filter.changed(FilterChange::Different);
+            }
+        });
+        SETTINGS.connect_apps_hide_idle(clone!(@strong refilter => move |_| refilter()));
+        SETTINGS.connect_apps_idle_cpu_threshold(clone!(@strong refilter => move |_| refilter()));
+        SETTINGS.connect_apps_idle_memory_threshold(move |_| refilter());
+
+        imp.regex_button
+            .connect_toggled(clone!(@strong self as this => move |_| {
+                this.update_search_regex();
+                let imp = this.imp();
+                imp.search_scores.borrow_mut().clear();
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
+                if let Some(sorter) = imp.sort_model.borrow().sorter() {
+                    sorter.changed(SorterChange::Different);
+                }
             }));
 
         imp.information_button
             .connect_clicked(clone!(@strong self as this => move |_| {
                 let imp = this.imp();
-                let selection_option = imp.selection_model.borrow()
-                .selected_item()
-                .map(|object| {
-                    object
-                    .downcast::<ApplicationEntry>()
-                    .unwrap()
-                });
-                if let Some(selection) = selection_option {
+                let selected_entries = this.get_selected_entries();
+                if let [selection] = selected_entries.as_slice() {
                     let app_dialog = ResAppDialog::new();
                     app_dialog.init(selection.app_item().as_ref().unwrap());
                     app_dialog.show();
@@ -524,121 +1130,866 @@ impl ResApplications {
 
         imp.end_application_button
             .connect_clicked(clone!(@strong self as this => move |_| {
-                if let Some(app) = this.get_selected_app_item() {
-                    this.execute_process_action_dialog(app, ProcessAction::TERM);
-                }
+                this.execute_process_action_dialog_apps(
+                    this.get_selected_app_items(),
+                    ProcessAction::TERM,
+                );
             }));
     }
 
-    fn search_filter(&self, obj: &Object) -> bool {
+    /// Calls `save_columns_state()` once the columns haven't changed for a
+    /// moment
+    fn schedule_save_columns_state(&self) {
         let imp = self.imp();
-        let item = obj.downcast_ref::<ApplicationEntry>().unwrap();
-        let search_string = imp.search_entry.text().to_string().to_lowercase();
-        !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
-            || item
-                .id()
-                .map(|id| id.to_lowercase().contains(&search_string))
-                .unwrap_or_default()
-            || item
-                .description()
-                .unwrap_or_default()
-                .to_lowercase()
-                .contains(&search_string)
-    }
-
-    fn get_selected_app_item(&self) -> Option<AppItem> {
-        self.imp()
-            .selection_model
-            .borrow()
-            .selected_item()
-            .and_then(|object| object.downcast::<ApplicationEntry>().unwrap().app_item())
+        if let Some(source) = imp.save_columns_source.take() {
+            source.remove();
+        }
+        let source = glib::timeout_add_local_once(
+            Duration::from_millis(500),
+            clone!(@weak self as this => move || {
+                this.imp().save_columns_source.take();
+                this.save_columns_state();
+            }),
+        );
+        *imp.save_columns_source.borrow_mut() = Some(source);
     }
 
-    pub fn refresh_apps_list(&self, apps: &AppsContext) {
+    /// Saves the sort column, sort order and column widths so that they can
+    /// be restored on the next launch
+    fn save_columns_state(&self) {
         let imp = self.imp();
+        let columns = imp.columns.borrow();
 
-        let store = imp.store.borrow_mut();
-        let mut dialog_opt = &*imp.open_dialog.borrow_mut();
+        let widths = columns
+            .iter()
+            .filter(|(_, _, column)| column.fixed_width() > 0)
+            .map(|(id, _, column)| ((*id).to_string(), column.fixed_width()))
+            .collect();
 
-        let mut new_items = apps.app_items();
-        let mut ids_to_remove = HashSet::new();
+        let (mut sort_column, mut ascending, _) = SETTINGS.applications_columns();
 
-        // change process entries of apps that have run before
-        store
-            .iter::<ApplicationEntry>()
-            .flatten()
-            .for_each(|object| {
-                let app_id = object.id().map(|gs| gs.to_string());
-                // filter out apps that have run before but don't anymore
-                if app_id.is_some() // don't try to filter out "System Processes"
-                    && !apps
-                        .get_app(&app_id.clone().unwrap_or_default())
-                        .unwrap()
-                        .is_running()
-                {
-                    if let Some((dialog_id, dialog)) = dialog_opt {
-                        if dialog_id.as_deref() == app_id.as_deref() {
-                            dialog.close();
-                            dialog_opt = &None;
-                        }
-                    }
-                    ids_to_remove.insert(app_id.clone());
-                }
-                if let Some((_, new_item)) = new_items.remove_entry(&app_id) {
-                    if let Some((dialog_id, dialog)) = dialog_opt {
-                        if *dialog_id == app_id {
-                            dialog.update(&new_item);
-                        }
-                    }
-                    object.update(new_item);
-                }
-            });
+        // the primary sort column can only be queried since GTK 4.10
+        if let Some(sorter) = imp
+            .column_view
+            .borrow()
+            .sorter()
+            .filter(|sorter| sorter.find_property("primary-sort-column").is_some())
+        {
+            let primary_column =
+                sorter.property::<Option<gtk::ColumnViewColumn>>("primary-sort-column");
+            if let Some((id, _, _)) = primary_column.and_then(|primary_column| {
+                columns
+                    .iter()
+                    .find(|(_, _, column)| *column == primary_column)
+            }) {
+                sort_column = (*id).to_string();
+                ascending =
+                    sorter.property::<SortType>("primary-sort-order") == SortType::Ascending;
+            }
+        }
 
-        // remove apps that recently have stopped running
-        store.retain(|object| {
-            !ids_to_remove.contains(
-                &object
-                    .clone()
-                    .downcast::<ApplicationEntry>()
-                    .unwrap()
-                    .id()
-                    .map(|gs| gs.to_string()),
-            )
-        });
+        let _ = SETTINGS.set_applications_columns(&(sort_column, ascending, widths));
+    }
 
-        // add the newly started apps to the store
-        new_items
-            .drain()
-            .for_each(|(_, new_item)| store.append(&ApplicationEntry::new(new_item)));
+    fn list_filter(&self, obj: &Object) -> bool {
+        let item = obj.downcast_ref::<ApplicationEntry>().unwrap();
+        self.selected_uid()
+            .map_or(true, |uid| item.has_process_of_user(uid))
+            && (!self.imp().search_revealer.reveals_child() || self.search_score(item).is_some())
+            && (!SETTINGS.apps_hide_idle() || !self.is_hidden_as_idle(item))
+    }
 
-        store.items_changed(0, store.n_items(), store.n_items());
+    /// Whether `item` uses so little processor time and memory that it's
+    /// hidden if idle apps are hidden. Selected apps are never hidden so that
+    /// they don't vanish while the user is about to do something with them.
+    fn is_hidden_as_idle(&self, item: &ApplicationEntry) -> bool {
+        let cpu_threshold = SETTINGS.apps_idle_cpu_threshold() as f32 / 100.0;
+        let memory_threshold = SETTINGS.apps_idle_memory_threshold() as u64 * 1_000_000;
+        item.cpu_usage() <= cpu_threshold
+            && item.memory_usage() <= memory_threshold
+            && !self
+                .imp()
+                .selected_ids
+                .borrow()
+                .contains(&item.id().map(|id| id.to_string()))
+    }
 
-        // -1 because we don't want to count System Processes
-        self.set_property(
-            "tab_subtitle",
-            i18n_f(
-                "Running Applications: {}",
-                &[&(store.n_items() - 1).to_string()],
-            ),
-        );
+    /// Returns the user whose apps should be shown or `None` if all apps
+    /// should be shown
+    fn selected_uid(&self) -> Option<u32> {
+        let imp = self.imp();
+        imp.user_filter_uids
+            .borrow()
+            .get(imp.user_drop_down.selected() as usize)
+            .copied()
+            .flatten()
     }
 
-    pub fn execute_process_action_dialog(&self, app: AppItem, action: ProcessAction) {
+    /// Offers the users in `uids` in the user filter while keeping the
+    /// current selection, even if that user doesn't run anything anymore
+    fn update_user_filter(&self, uids: &[u32]) {
         let imp = self.imp();
 
-        // Nothing too bad can happen on Continue so dont show the dialog
-        if action == ProcessAction::CONT {
-            send!(
-                imp.sender.get().unwrap(),
-                Action::ManipulateApp(action, app.id.unwrap(), self.imp().toast_overlay.get())
-            );
+        let selected_uid = self.selected_uid();
+
+        let mut users = uids
+            .iter()
+            .copied()
+            .chain(selected_uid)
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .map(|uid| {
+                let name = uzers::get_user_by_uid(uid).map_or_else(
+                    || uid.to_string(),
+                    |user| user.name().to_string_lossy().to_string(),
+                );
+                (uid, name)
+            })
+            .collect::<Vec<_>>();
+        users.sort_by(|(_, a), (_, b)| a.cmp(b));
+
+        let new_uids = std::iter::once(None)
+            .chain(users.iter().map(|(uid, _)| Some(*uid)))
+            .collect::<Vec<_>>();
+        if *imp.user_filter_uids.borrow() == new_uids {
             return;
         }
 
-        // Confirmation dialog & warning
-        let dialog = adw::MessageDialog::builder()
-            .transient_for(&MainWindow::default())
+        let names = std::iter::once(i18n("All Users"))
+            .chain(users.into_iter().map(|(_, name)| name))
+            .collect::<Vec<_>>();
+        let selected = new_uids
+            .iter()
+            .position(|uid| *uid == selected_uid)
+            .unwrap_or_default();
+
+        *imp.user_filter_uids.borrow_mut() = new_uids;
+        imp.user_drop_down.set_model(Some(&gtk::StringList::new(
+            &names.iter().map(String::as_str).collect::<Vec<_>>(),
+        )));
+        imp.user_drop_down.set_selected(selected as u32);
+    }
+
+    /// Compiles the search into `search_regex` if regular expressions are
+    /// enabled and marks the search entry if it's not a valid one
+    fn update_search_regex(&self) {
+        let imp = self.imp();
+
+        let regex = if imp.regex_button.is_active() {
+            RegexBuilder::new(&imp.search_entry.text())
+                .case_insensitive(true)
+                .build()
+                .map(Some)
+        } else {
+            Ok(None)
+        };
+
+        if let Ok(regex) = regex {
+            imp.search_entry.remove_css_class("error");
+            *imp.search_regex.borrow_mut() = regex;
+        } else {
+            imp.search_entry.add_css_class("error");
+            *imp.search_regex.borrow_mut() = None;
+        }
+    }
+
+    /// Returns how well `item` matches the current search, or `None` if it
+    /// doesn't match at all. Names and IDs are matched fuzzily, descriptions
+    /// are too long for that and only match if they contain the search.
+    ///
+    /// In regex mode, every item whose name, ID or description matches gets
+    /// the same score. An invalid regex doesn't filter anything.
+    ///
+    /// The score is cached per app until the search changes because both the
+    /// filter and the sorter need it.
+    fn search_score(&self, item: &ApplicationEntry) -> Option<i32> {
+        let imp = self.imp();
+        let id = item.id();
+
+        if let Some(score) = imp.search_scores.borrow().get(&id) {
+            return *score;
+        }
+
+        let score = self.compute_search_score(item);
+        imp.search_scores.borrow_mut().insert(id, score);
+        score
+    }
+
+    /// Uncached version of `search_score()`
+    fn compute_search_score(&self, item: &ApplicationEntry) -> Option<i32> {
+        let imp = self.imp();
+
+        if imp.regex_button.is_active() {
+            return match &*imp.search_regex.borrow() {
+                Some(regex) => (regex.is_match(&item.name())
+                    || item.id().is_some_and(|id| regex.is_match(&id))
+                    || item
+                        .description()
+                        .is_some_and(|description| regex.is_match(&description)))
+                .then_some(0),
+                None => Some(0),
+            };
+        }
+
+        let search_string = imp.search_entry.text().to_lowercase();
+
+        let name_score = fuzzy_score(&search_string, item.name());
+        let id_score = item.id().and_then(|id| fuzzy_score(&search_string, id));
+        let description_score = item
+            .description()
+            .filter(|description| description.to_lowercase().contains(&search_string))
+            .map(|_| 0);
+
+        name_score.max(id_score).max(description_score)
+    }
+
+    /// Shows the combined memory and processor usage of all applications
+    /// that are currently visible
+    fn update_summary(&self) {
+        let imp = self.imp();
+
+        let (memory_usage, cpu_usage) = imp
+            .filter_model
+            .borrow()
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .fold((0, 0.0), |(memory_usage, cpu_usage), entry| {
+                (
+                    memory_usage + entry.memory_usage(),
+                    cpu_usage + entry.cpu_usage(),
+                )
+            });
+
+        imp.summary.set_label(&i18n_f(
+            "Visible applications use {} of memory and {} of processor time",
+            &[
+                &convert_storage(memory_usage as f64, false),
+                &convert_cpu_usage(cpu_usage),
+            ],
+        ));
+    }
+
+    fn get_selected_entries(&self) -> Vec<ApplicationEntry> {
+        let selection_model = self.imp().selection_model.borrow();
+        (0..selection_model.n_items())
+            .filter(|position| selection_model.is_selected(*position))
+            .filter_map(|position| selection_model.item(position).and_downcast())
+            .collect()
+    }
+
+    /// Returns all selected apps in the order they're displayed in
+    fn get_selected_app_items(&self) -> Vec<AppItem> {
+        self.get_selected_entries()
+            .into_iter()
+            .filter_map(|entry| entry.app_item())
+            .collect()
+    }
+
+    /// Offers pinning if any of the selected apps isn't pinned yet and
+    /// unpinning if any of them is
+    fn update_pin_actions(&self, selected: &[AppItem]) {
+        let pinned_apps = self.imp().pinned_apps.borrow();
+        let (any_pinned, any_unpinned) = selected.iter().filter_map(|app| app.id.as_deref()).fold(
+            (false, false),
+            |(pinned, unpinned), id| {
+                let is_pinned = pinned_apps.contains(id);
+                (pinned || is_pinned, unpinned || !is_pinned)
+            },
+        );
+        self.action_set_enabled("applications.pin-application", any_unpinned);
+        self.action_set_enabled("applications.unpin-application", any_pinned);
+    }
+
+    fn set_selected_apps_pinned(&self, pinned: bool) {
+        let ids = self
+            .get_selected_app_items()
+            .into_iter()
+            .filter_map(|app| app.id)
+            .collect::<Vec<_>>();
+        if ids.is_empty() {
+            return;
+        }
+
+        let mut pinned_apps = SETTINGS.apps_pinned();
+        if pinned {
+            for id in ids {
+                if !pinned_apps.contains(&id) {
+                    pinned_apps.push(id);
+                }
+            }
+        } else {
+            pinned_apps.retain(|id| !ids.contains(id));
+        }
+
+        if let Err(err) = SETTINGS.set_apps_pinned(&pinned_apps) {
+            error!("Unable to save pinned apps: {err}");
+        }
+    }
+
+    /// Returns the selected app if exactly one is selected, actions that
+    /// don't make sense for several apps at once use this
+    fn get_selected_app_item(&self) -> Option<AppItem> {
+        let mut selected = self.get_selected_app_items();
+        if selected.len() == 1 {
+            selected.pop()
+        } else {
+            None
+        }
+    }
+
+    /// Returns the PIDs of the app whose dialog is currently shown so that
+    /// the refresh task can gather its remote addresses
+    pub fn open_dialog_pids(&self) -> Option<Vec<i32>> {
+        let imp = self.imp();
+        let open_dialog = imp.open_dialog.borrow();
+        let (dialog_id, dialog) = open_dialog.as_ref()?;
+        if !dialog.is_visible() {
+            return None;
+        }
+        imp.store
+            .borrow()
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .find(|entry| entry.id().map(|gs| gs.to_string()) == *dialog_id)
+            .and_then(|entry| entry.app_item())
+            .map(|app_item| app_item.pids)
+    }
+
+    pub fn refresh_dialog_remote_addresses(&self, remote_addresses: Vec<String>) {
+        if let Some((_, dialog)) = &*self.imp().open_dialog.borrow() {
+            dialog.update_remote_addresses(remote_addresses);
+        }
+    }
+
+    pub fn refresh_apps_list(&self, apps: &AppsContext) {
+        let imp = self.imp();
+
+        let store = imp.store.borrow_mut();
+        let dialog_opt = &*imp.open_dialog.borrow_mut();
+
+        let displayed_ids = store
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .map(|object| object.id().map(|gs| gs.to_string()))
+            .collect::<HashSet<_>>();
+
+        let AppItemsDiff {
+            added,
+            removed,
+            mut updated,
+        } = apps.app_items_diff(&displayed_ids);
+
+        if let Some((dialog_id, dialog)) = dialog_opt {
+            if removed.contains(dialog_id) {
+                dialog.close();
+            } else if let Some(new_item) = updated.get(dialog_id) {
+                dialog.update(new_item);
+                dialog.update_processes(
+                    &new_item
+                        .pids
+                        .iter()
+                        .filter_map(|pid| apps.process_item(*pid))
+                        .collect::<Vec<_>>(),
+                );
+            }
+        }
+
+        // remove apps that recently have stopped running, back to front so
+        // that the positions of the remaining ones stay valid
+        for position in (0..store.n_items()).rev() {
+            let is_removed = store
+                .item(position)
+                .and_downcast::<ApplicationEntry>()
+                .is_some_and(|object| removed.contains(&object.id().map(|gs| gs.to_string())));
+            if is_removed {
+                store.remove(position);
+            }
+        }
+
+        // change the values of apps that have run before in place, this keeps
+        // the selection and scroll position intact
+        store
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .for_each(|object| {
+                if let Some(new_item) = updated.remove(&object.id().map(|gs| gs.to_string())) {
+                    object.update(new_item);
+                }
+            });
+
+        // add the newly started apps to the store
+        let added = added
+            .into_iter()
+            .map(ApplicationEntry::new)
+            .collect::<Vec<_>>();
+        store.splice(store.n_items(), 0, &added);
+
+        self.check_memory_alerts(&store);
+
+        self.update_user_filter(&apps.active_uids());
+
+        // the users and usages of updated apps might have changed
+        if self.selected_uid().is_some() || SETTINGS.apps_hide_idle() {
+            if let Some(filter) = imp.filter_model.borrow().filter() {
+                filter.changed(FilterChange::Different);
+            }
+        }
+
+        // the values the columns are sorted by have most likely changed
+        if let Some(sorter) = imp.sort_model.borrow().sorter() {
+            sorter.changed(SorterChange::Different);
+        }
+
+        // System Processes don't count, they're not shown at all when grouping
+        // by executable
+        let running_apps = store
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .filter(|entry| entry.id().is_some())
+            .count();
+        self.set_property(
+            "tab_subtitle",
+            i18n_f("Running Applications: {}", &[&running_apps.to_string()]),
+        );
+
+        self.update_summary();
+    }
+
+    /// Lets the user pick a signal that is then sent to all processes of `app`
+    pub fn send_signal_dialog(&self, app: AppItem) {
+        if !app.is_app() {
+            return;
+        }
+
+        let signal_names = SIGNALS
+            .iter()
+            .map(|signal| signal.as_str())
+            .collect::<Vec<_>>();
+        let drop_down = gtk::DropDown::from_strings(&signal_names);
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(i18n_f("Send Signal to {}", &[&app.display_name]))
+            .extra_child(&drop_down)
+            .build();
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.add_response("send", &i18n("Send"));
+        dialog.set_response_appearance("send", ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("send"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this, @strong app, @weak drop_down => move |_, response| {
+                if response == "send" {
+                    if let Some(signal) = SIGNALS.get(drop_down.selected() as usize) {
+                        this.execute_process_action_dialog(app.clone(), ProcessAction::SIGNAL(*signal));
+                    }
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Opens the working directory of `app` in the default file manager. The
+    /// processes of an app may be in different directories, so the one of the
+    /// process that was started first (usually the one with the lowest PID) is
+    /// used.
+    pub fn open_working_directory(&self, app: &AppItem) {
+        let imp = self.imp();
+
+        let mut pids = app.pids.clone();
+        pids.sort_unstable();
+        // reading the link of other users' processes requires privileges
+        let Some(cwd) = pids
+            .iter()
+            .find_map(|pid| std::fs::read_link(format!("/proc/{pid}/cwd")).ok())
+        else {
+            imp.toast_overlay.add_toast(adw::Toast::new(&i18n_f(
+                "Unable to access the working directory of {}",
+                &[&app.display_name],
+            )));
+            return;
+        };
+
+        let uri = gio::File::for_path(&cwd).uri();
+        let context = self.display().app_launch_context();
+        if let Err(error) = gio::AppInfo::launch_default_for_uri(&uri, Some(&context)) {
+            error!("Unable to open {}: {error}", cwd.display());
+            imp.toast_overlay.add_toast(adw::Toast::new(&i18n_f(
+                "Unable to open {}",
+                &[&cwd.to_string_lossy()],
+            )));
+        }
+    }
+
+    /// Lets the user pick a new nice value for all processes of `app`
+    pub fn change_priority_dialog(&self, app: AppItem) {
+        let Some(id) = app.id.clone() else {
+            return;
+        };
+
+        let spin_button = gtk::SpinButton::with_range(-20.0, 19.0, 1.0);
+        spin_button.set_value(f64::from(app.nice));
+        spin_button.set_tooltip_text(Some(&i18n(
+            "Lower values mean a higher priority, lowering it requires elevated privileges",
+        )));
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(i18n_f("Change Priority of {}", &[&app.display_name]))
+            .extra_child(&spin_button)
+            .build();
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.add_response("change", &i18n("Change"));
+        dialog.set_response_appearance("change", ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("change"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this, @weak spin_button => move |_, response| {
+                if response == "change" {
+                    let imp = this.imp();
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::ReniceApp(spin_button.value_as_int(), id.clone(), imp.toast_overlay.get())
+                    );
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Lets the user pick a memory usage above which a notification is sent
+    /// for `app`
+    pub fn memory_alert_dialog(&self, app: AppItem) {
+        let Some(id) = app.id.clone() else {
+            return;
+        };
+
+        let (unit, unit_name) = match SETTINGS.base() {
+            Base::Decimal => (1e9, i18n("GB")),
+            Base::Binary => (1024.0 * 1024.0 * 1024.0, i18n("GiB")),
+        };
+
+        let spin_button = gtk::SpinButton::with_range(0.1, 1024.0, 0.5);
+        spin_button.set_digits(1);
+        spin_button.set_hexpand(true);
+        let threshold = SETTINGS.apps_memory_alerts().get(&id).copied();
+        spin_button.set_value(threshold.map_or_else(
+            || (app.memory_usage as f64 / unit).ceil().max(1.0),
+            |threshold| threshold as f64 / unit,
+        ));
+
+        let extra_child = gtk::Box::new(gtk::Orientation::Horizontal, 12);
+        extra_child.append(&spin_button);
+        extra_child.append(&gtk::Label::new(Some(&unit_name)));
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(i18n_f("Memory Alert for {}", &[&app.display_name]))
+            .body(i18n(
+                "You will be notified when the application uses more memory than this",
+            ))
+            .extra_child(&extra_child)
+            .build();
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.add_response("set", &i18n("Set Alert"));
+        dialog.set_response_appearance("set", ResponseAppearance::Suggested);
+        dialog.set_default_response(Some("set"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this, @weak spin_button => move |_, response| {
+                if response == "set" {
+                    this.set_memory_alert(&id, Some((spin_button.value() * unit) as u64));
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Saves the memory alert threshold of the app with `id` in bytes, `None`
+    /// removes it
+    fn set_memory_alert(&self, id: &str, threshold: Option<u64>) {
+        let mut memory_alerts = SETTINGS.apps_memory_alerts();
+        if let Some(threshold) = threshold {
+            memory_alerts.insert(id.to_string(), threshold);
+        } else {
+            memory_alerts.remove(id);
+        }
+
+        if let Err(err) = SETTINGS.set_apps_memory_alerts(&memory_alerts) {
+            error!("Unable to save memory alerts: {err}");
+        }
+
+        // notify again if the app is above its new threshold
+        self.imp().exceeded_memory_alerts.borrow_mut().remove(id);
+        self.update_memory_alert_action();
+    }
+
+    fn update_memory_alert_action(&self) {
+        let has_alert = self
+            .get_selected_app_item()
+            .and_then(|app| app.id)
+            .is_some_and(|id| SETTINGS.apps_memory_alerts().contains_key(&id));
+        self.action_set_enabled("applications.remove-memory-alert", has_alert);
+    }
+
+    /// Sends a notification for every app in `store` whose memory usage has
+    /// risen above its alert threshold. Apps are only notified about once
+    /// until their usage falls below the threshold again.
+    fn check_memory_alerts(&self, store: &gio::ListStore) {
+        let memory_alerts = SETTINGS.apps_memory_alerts();
+        let mut exceeded_memory_alerts = self.imp().exceeded_memory_alerts.borrow_mut();
+
+        if memory_alerts.is_empty() {
+            exceeded_memory_alerts.clear();
+            return;
+        }
+
+        for entry in store.iter::<ApplicationEntry>().flatten() {
+            let Some(id) = entry.id().map(|id| id.to_string()) else {
+                continue;
+            };
+            let Some(threshold) = memory_alerts.get(&id).copied() else {
+                exceeded_memory_alerts.remove(&id);
+                continue;
+            };
+
+            if entry.memory_usage() <= threshold {
+                exceeded_memory_alerts.remove(&id);
+            } else if exceeded_memory_alerts.insert(id.clone()) {
+                let base = SETTINGS.base();
+                let notification = gio::Notification::new(&i18n_f(
+                    "{} is using a lot of memory",
+                    &[&entry.name()],
+                ));
+                notification.set_body(Some(&i18n_f(
+                    "The application is using {}, more than its alert threshold of {}",
+                    &[
+                        &format_bytes(entry.memory_usage() as f64, base, COLUMN_PRECISION),
+                        &format_bytes(threshold as f64, base, COLUMN_PRECISION),
+                    ],
+                )));
+                Application::default()
+                    .send_notification(Some(&format!("memory-alert-{id}")), &notification);
+            }
+        }
+    }
+
+    /// Returns the items that are currently visible, i.e. that match the
+    /// search, in the order they're displayed in
+    pub fn visible_app_items(&self) -> Vec<AppItem> {
+        self.imp()
+            .sort_model
+            .borrow()
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .filter_map(|entry| entry.app_item())
+            .collect()
+    }
+
+    /// Asks for confirmation and then ends all apps that currently match the
+    /// search, System Processes are never included
+    pub fn end_visible_apps_dialog(&self) {
+        let imp = self.imp();
+
+        let apps: Vec<AppItem> = imp
+            .filter_model
+            .borrow()
+            .iter::<ApplicationEntry>()
+            .flatten()
+            .filter_map(|entry| entry.app_item())
+            .filter(AppItem::is_app)
+            .collect();
+
+        if apps.is_empty() {
+            return;
+        }
+
+        let apps_amount = apps.len() as u32;
+        let processes_amount: usize = apps.iter().map(|app| app.processes_amount).sum();
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(ni18n_f(
+                "End {} application?",
+                "End {} applications?",
+                apps_amount,
+                &[&apps_amount.to_string()],
+            ))
+            .body(ni18n_f(
+                "This will end {} process. Unsaved work might be lost.",
+                "This will end {} processes. Unsaved work might be lost.",
+                processes_amount as u32,
+                &[&processes_amount.to_string()],
+            ))
+            .build();
+
+        dialog.add_response("yes", &i18n("End applications"));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        let ids: Vec<String> = apps.into_iter().filter_map(|app| app.id).collect();
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this => move |_, response| {
+                if response == "yes" {
+                    let imp = this.imp();
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::ManipulateApps(ProcessAction::TERM, ids.clone(), imp.toast_overlay.get())
+                    );
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Asks whether `app` should be ended and killed if it's still running
+    /// after the grace period
+    pub fn end_app_forcefully_dialog(&self, app: AppItem) {
+        let Some(id) = app.id.clone() else {
+            return;
+        };
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(window::get_action_name(ProcessAction::TERM, &[&app.display_name]))
+            .body(ni18n_f(
+                "Processes that are still running after {} second will be killed. Unsaved work might be lost.",
+                "Processes that are still running after {} seconds will be killed. Unsaved work might be lost.",
+                SETTINGS.end_grace_period() as u32,
+                &[&SETTINGS.end_grace_period().to_string()],
+            ))
+            .build();
+
+        dialog.add_response("yes", &i18n("End Forcefully"));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this => move |_, response| {
+                if response == "yes" {
+                    let imp = this.imp();
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::EndAppForcefully(id.clone(), imp.toast_overlay.get())
+                    );
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    /// Asks for confirmation and then applies `action` to the selected apps.
+    /// Does nothing if the search entry has the focus, so that keyboard
+    /// shortcuts like Delete keep editing the search text.
+    pub fn execute_process_action_dialog_selected_apps(&self, action: ProcessAction) {
+        if self.imp().search_entry.focus_child().is_some() {
+            return;
+        }
+
+        self.execute_process_action_dialog_apps(self.get_selected_app_items(), action);
+    }
+
+    /// Asks for confirmation and then applies `action` to all of `apps` at
+    /// once. System Processes are skipped.
+    pub fn execute_process_action_dialog_apps(&self, apps: Vec<AppItem>, action: ProcessAction) {
+        let imp = self.imp();
+
+        let mut apps: Vec<AppItem> = apps.into_iter().filter(AppItem::is_app).collect();
+        if apps.len() <= 1 {
+            if let Some(app) = apps.pop() {
+                self.execute_process_action_dialog(app, action);
+            }
+            return;
+        }
+
+        let apps_amount = apps.len() as u32;
+        let ids: Vec<String> = apps.into_iter().filter_map(|app| app.id).collect();
+
+        if !window::needs_confirmation(action) {
+            send!(
+                imp.sender.get().unwrap(),
+                Action::ManipulateApps(action, ids, imp.toast_overlay.get())
+            );
+            return;
+        }
+
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
+            .modal(true)
+            .heading(window::get_apps_action_name(action, apps_amount))
+            .body(window::get_app_action_warning(action))
+            .build();
+
+        dialog.add_response("yes", &window::get_apps_action_description(action));
+        dialog.set_response_appearance("yes", ResponseAppearance::Destructive);
+
+        dialog.add_response("no", &i18n("Cancel"));
+        dialog.set_default_response(Some("no"));
+        dialog.set_close_response("no");
+
+        dialog.connect_response(
+            None,
+            clone!(@strong self as this => move |_, response| {
+                if response == "yes" {
+                    let imp = this.imp();
+                    send!(
+                        imp.sender.get().unwrap(),
+                        Action::ManipulateApps(action, ids.clone(), imp.toast_overlay.get())
+                    );
+                }
+            }),
+        );
+
+        dialog.show();
+    }
+
+    pub fn execute_process_action_dialog(&self, app: AppItem, action: ProcessAction) {
+        let imp = self.imp();
+
+        // System Processes can't be acted on as a whole
+        let Some(id) = app.id.clone() else {
+            return;
+        };
+
+        if !window::needs_confirmation(action) {
+            send!(
+                imp.sender.get().unwrap(),
+                Action::ManipulateApp(action, id, imp.toast_overlay.get())
+            );
+            return;
+        }
+
+        // Confirmation dialog & warning
+        let dialog = adw::MessageDialog::builder()
+            .transient_for(&MainWindow::default())
             .modal(true)
             .heading(window::get_action_name(action, &[&app.display_name]))
             .body(window::get_app_action_warning(action))
@@ -654,12 +2005,12 @@ impl ResApplications {
         // Called when "yes" or "no" were clicked
         dialog.connect_response(
             None,
-            clone!(@strong self as this, @strong app => move |_, response| {
+            clone!(@strong self as this, @strong id => move |_, response| {
                 if response == "yes" {
                     let imp = this.imp();
                     send!(
                         imp.sender.get().unwrap(),
-                        Action::ManipulateApp(action, app.id.clone().unwrap(), imp.toast_overlay.get())
+                        Action::ManipulateApp(action, id.clone(), imp.toast_overlay.get())
                     );
                 }
             }),
@@ -668,3 +2019,48 @@ impl ResApplications {
         dialog.show();
     }
 }
+
+impl ContextMenuPage for ResApplications {
+    fn context_menu(&self) -> Option<gtk::PopoverMenu> {
+        self.imp().context_menu.get().cloned()
+    }
+
+    fn column_view(&self) -> gtk::ColumnView {
+        self.imp().column_view.borrow().clone()
+    }
+
+    /// If the row is part of a larger selection, the selection is kept so the
+    /// menu acts on all of it
+    fn select_for_context_menu(&self, position: u32) {
+        let selection_model = self.imp().selection_model.borrow();
+        if !selection_model.is_selected(position) {
+            selection_model.select_item(position, true);
+        }
+    }
+}
+
+/// Keeps the accessible label of `cell` in sync with its text and prefixes it
+/// with the column's title, so that screen readers announce "Memory, 1.2 GB"
+/// instead of a bare number
+fn label_cell_for_screen_readers(cell: &gtk::Inscription, column: String) {
+    cell.connect_text_notify(move |cell| {
+        let label = format!("{column}, {}", cell.text().unwrap_or_default());
+        cell.update_property(&[gtk::accessible::Property::Label(&label)]);
+    });
+}
+
+/// The tooltip of the memory column, the column itself only shows the
+/// memory that isn't shared
+fn memory_breakdown(app: &AppItem) -> String {
+    let format = |bytes: usize| format_bytes(bytes as f64, SETTINGS.base(), COLUMN_PRECISION);
+    [
+        i18n_f(
+            "Resident: {}",
+            &[&format(app.memory_usage + app.shared_memory_usage)],
+        ),
+        i18n_f("Private: {}", &[&format(app.memory_usage)]),
+        i18n_f("Shared: {}", &[&format(app.shared_memory_usage)]),
+        i18n_f("Swap: {}", &[&format(app.swap_usage)]),
+    ]
+    .join("\n")
+}
@@ -0,0 +1,54 @@
+use adw::prelude::*;
+use gtk::glib::{self, clone};
+use gtk::{gdk, gio, Widget};
+
+/// A page with a `gtk::ColumnView` whose rows have a context menu
+pub trait ContextMenuPage: IsA<Widget> {
+    /// The menu that was created with `new_context_menu()`
+    fn context_menu(&self) -> Option<gtk::PopoverMenu>;
+
+    fn column_view(&self) -> gtk::ColumnView;
+
+    /// Selects the row at `position` before the context menu pops up, so
+    /// that the menu's actions apply to it
+    fn select_for_context_menu(&self, position: u32);
+
+    /// Selects the row at `position` and pops up the context menu at the
+    /// coordinates `x` and `y` relative to `widget`
+    fn popup_context_menu(&self, position: u32, widget: &Widget, x: f64, y: f64) {
+        self.select_for_context_menu(position);
+        if let (Some(context_menu), Some((x, y))) = (
+            self.context_menu(),
+            widget.translate_coordinates(&self.column_view(), x, y),
+        ) {
+            context_menu.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+            context_menu.popup();
+        }
+    }
+}
+
+/// Creates the context menu for the rows of `column_view` from `model`
+pub fn new_context_menu(model: &gio::MenuModel, column_view: &gtk::ColumnView) -> gtk::PopoverMenu {
+    let context_menu = gtk::PopoverMenu::from_model(Some(model));
+    context_menu.set_parent(column_view);
+    context_menu.set_has_arrow(false);
+    context_menu.set_halign(gtk::Align::Start);
+    context_menu
+}
+
+/// Makes `widget`, which is part of the row of `list_item`, pop up the
+/// context menu of the page `P` it's in when it is right-clicked
+pub fn add_context_menu_gesture<P: ContextMenuPage>(
+    list_item: &gtk::ListItem,
+    widget: &impl IsA<Widget>,
+) {
+    let gesture = gtk::GestureClick::new();
+    gesture.set_button(gdk::BUTTON_SECONDARY);
+    gesture.connect_pressed(clone!(@weak list_item => move |gesture, _, x, y| {
+        let widget = gesture.widget();
+        if let Some(page) = widget.ancestor(P::static_type()).and_downcast::<P>() {
+            page.popup_context_menu(list_item.position(), &widget, x, y);
+        }
+    }));
+    widget.add_controller(gesture);
+}
@@ -4,15 +4,19 @@ use gtk::glib::{self, clone};
 use gtk::FlowBoxChild;
 
 use crate::config::PROFILE;
-use crate::i18n::{i18n, i18n_f};
+use crate::i18n::{i18n, i18n_f, ni18n_f};
 use crate::ui::widgets::graph_box::ResGraphBox;
-use crate::utils::cpu::CpuData;
+use crate::utils::cpu::{CpuData, CpuTimes, ThrottleCounts};
 use crate::utils::settings::SETTINGS;
+use crate::utils::smoothing::{smoothing_alpha, Ema};
 use crate::utils::units::{convert_frequency, convert_temperature};
 use crate::utils::{cpu, NaNDefault};
 
 mod imp {
-    use std::cell::{Cell, RefCell};
+    use std::{
+        cell::{Cell, RefCell},
+        time::Duration,
+    };
 
     use crate::ui::widgets::graph_box::ResGraphBox;
 
@@ -53,11 +57,32 @@ mod imp {
         #[template_child]
         pub architecture: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub byte_order: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub address_sizes: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub temperature: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub usage_breakdown: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub throttling: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub throttling_warning: TemplateChild<gtk::Image>,
+        #[template_child]
+        pub copy_info_button: TemplateChild<gtk::Button>,
         pub thread_graphs: RefCell<Vec<ResGraphBox>>,
         pub old_total_usage: Cell<(u64, u64)>,
+        pub old_total_breakdown: Cell<CpuTimes>,
+        pub old_throttle_counts: Cell<Option<ThrottleCounts>>,
         pub old_thread_usages: RefCell<Vec<(u64, u64)>>,
+        pub total_usage_ema: Cell<Ema>,
+        pub thread_usage_emas: RefCell<Vec<Ema>>,
         pub logical_cpus_amount: Cell<usize>,
+        pub critical_temperature: Cell<Option<f32>>,
+        /// When the page was last refreshed according to `cpu::boot_clock()`
+        pub last_refresh: Cell<Option<Duration>>,
+        /// The properties as plain text for copying them
+        pub info_text: RefCell<String>,
 
         #[property(get)]
         uses_progress_bar: Cell<bool>,
@@ -110,7 +135,12 @@ mod imp {
                 sockets: Default::default(),
                 virtualization: Default::default(),
                 architecture: Default::default(),
+                byte_order: Default::default(),
+                address_sizes: Default::default(),
                 temperature: Default::default(),
+                usage_breakdown: Default::default(),
+                throttling: Default::default(),
+                throttling_warning: Default::default(),
                 thread_graphs: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(ThemedIcon::new("processor-symbolic").into()),
@@ -118,8 +148,16 @@ mod imp {
                 tab_name: Cell::new(glib::GString::from(i18n("Processor"))),
                 tab_subtitle: Cell::new(glib::GString::from("")),
                 old_total_usage: Cell::default(),
+                old_total_breakdown: Cell::default(),
+                old_throttle_counts: Cell::default(),
                 old_thread_usages: RefCell::default(),
+                total_usage_ema: Cell::default(),
+                thread_usage_emas: RefCell::default(),
                 logical_cpus_amount: Cell::default(),
+                critical_temperature: Cell::default(),
+                last_refresh: Cell::default(),
+                info_text: RefCell::default(),
+                copy_info_button: Default::default(),
             }
         }
     }
@@ -193,12 +231,31 @@ impl ResCPU {
 
         let old_total_usage = cpu::get_cpu_usage(None).await.unwrap_or((0, 0));
         imp.old_total_usage.set(old_total_usage);
+        imp.old_total_breakdown
+            .set(cpu::get_cpu_breakdown(None).await.unwrap_or_default());
+        imp.old_throttle_counts
+            .set(cpu::get_throttle_counts().await);
+
+        // this also determines which sensor is used for the CPU temperature
+        let _ = cpu::get_temperature().await;
+        if let Some(zone) = cpu::cpu_thermal_zone() {
+            let critical_temperature = cpu::thermal_trip_points(zone)
+                .await
+                .unwrap_or_default()
+                .into_iter()
+                .find(|(trip_type, _)| trip_type == "critical")
+                .map(|(_, temp)| temp);
+            imp.critical_temperature.set(critical_temperature);
+        }
+
+        let cpu_info_text = cpu::cpu_info_to_text(&cpu_info);
 
         let logical_cpus = cpu_info.logical_cpus.unwrap_or(0);
-        for i in 0..logical_cpus {
-            let old_thread_usage = cpu::get_cpu_usage(Some(i)).await.unwrap_or((0, 0));
-            imp.old_thread_usages.borrow_mut().push(old_thread_usage);
-        }
+        let mut old_thread_usages = cpu::get_all_cpu_usages().await.unwrap_or_default();
+        old_thread_usages.resize(logical_cpus, (0, 0));
+        imp.old_thread_usages.replace(old_thread_usages);
+        imp.thread_usage_emas
+            .replace(vec![Ema::default(); logical_cpus]);
 
         imp.logical_cpus_amount.set(logical_cpus);
 
@@ -233,11 +290,17 @@ impl ResCPU {
                 .map_or_else(|| i18n("N/A"), |x| convert_frequency(x as f64)),
         );
 
-        imp.logical_cpus.set_subtitle(
-            &cpu_info
-                .logical_cpus
-                .map_or_else(|| i18n("N/A"), |x| x.to_string()),
-        );
+        let logical_cpus_string = cpu_info
+            .logical_cpus
+            .map_or_else(|| i18n("N/A"), |x| x.to_string());
+        if let Some(quota) = cpu::get_cpu_quota() {
+            imp.logical_cpus.set_subtitle(&i18n_f(
+                "{} (limited to {})",
+                &[&logical_cpus_string, &format!("{quota:.2}")],
+            ));
+        } else {
+            imp.logical_cpus.set_subtitle(&logical_cpus_string);
+        }
 
         imp.physical_cpus.set_subtitle(
             &cpu_info
@@ -256,6 +319,14 @@ impl ResCPU {
 
         imp.architecture
             .set_subtitle(&cpu_info.architecture.unwrap_or_else(|| i18n("N/A")));
+
+        imp.byte_order
+            .set_subtitle(&cpu_info.byte_order.unwrap_or_else(|| i18n("N/A")));
+
+        imp.address_sizes
+            .set_subtitle(&cpu_info.address_sizes.unwrap_or_else(|| i18n("N/A")));
+
+        imp.info_text.replace(cpu_info_text);
     }
 
     pub fn setup_signals(&self) {
@@ -272,18 +343,59 @@ impl ResCPU {
             }));
 
         imp.logical_switch.set_active(SETTINGS.show_logical_cpus());
+
+        imp.copy_info_button
+            .connect_clicked(clone!(@weak self as this => move |_| {
+                this.clipboard().set_text(&this.imp().info_text.borrow());
+            }));
     }
 
     pub fn refresh_page(&self, cpu_data: &CpuData) {
         let CpuData {
             new_total_usage,
+            new_total_breakdown,
             new_thread_usages,
             temperature,
             frequencies,
+            throttle_counts,
         } = cpu_data;
 
         let imp = self.imp();
 
+        // keep the previous counters if /proc/stat couldn't be read, starting
+        // over from 0 would show the average since boot on the next refresh
+        let new_total_usage = new_total_usage
+            .as_ref()
+            .copied()
+            .unwrap_or_else(|_| imp.old_total_usage.get());
+
+        // the first sample after resuming from suspend would show a bogus spike,
+        // so only take the current counters as the new baseline
+        let now = cpu::boot_clock();
+        let resumed = imp
+            .last_refresh
+            .replace(now)
+            .zip(now)
+            .is_some_and(|(last, now)| {
+                cpu::resumed_from_suspend(last, now, SETTINGS.refresh_speed().ui_refresh_interval())
+            });
+        if resumed {
+            imp.old_total_usage.set(new_total_usage);
+            for (old_thread_usage, new_thread_usage) in imp
+                .old_thread_usages
+                .borrow_mut()
+                .iter_mut()
+                .zip(new_thread_usages)
+            {
+                *old_thread_usage = *new_thread_usage;
+            }
+            if let Ok(new_total_breakdown) = new_total_breakdown {
+                imp.old_total_breakdown.set(*new_total_breakdown);
+            }
+            imp.old_throttle_counts.set(*throttle_counts);
+            return;
+        }
+
         let idle_total_delta = new_total_usage
             .0
             .saturating_sub(imp.old_total_usage.get().0);
@@ -292,13 +404,20 @@ impl ResCPU {
             .saturating_sub(imp.old_total_usage.get().1);
         let work_total_time = sum_total_delta.saturating_sub(idle_total_delta);
 
-        let total_fraction = ((work_total_time as f64) / (sum_total_delta as f64)).nan_default(0.0);
+        let alpha = smoothing_alpha();
+
+        let mut total_usage_ema = imp.total_usage_ema.get();
+        let total_fraction = total_usage_ema.push(
+            ((work_total_time as f64) / (sum_total_delta as f64)).nan_default(0.0),
+            alpha,
+        );
+        imp.total_usage_ema.set(total_usage_ema);
 
         imp.total_cpu.push_data_point(total_fraction);
 
         let percentage_string = &format!("{} %", (total_fraction * 100.0).round());
         imp.total_cpu.set_subtitle(percentage_string);
-        imp.old_total_usage.set(*new_total_usage);
+        imp.old_total_usage.set(new_total_usage);
 
         if imp.logical_cpus_amount.get() > 1 {
             for (i, old_thread_usage) in imp
@@ -313,26 +432,105 @@ impl ResCPU {
                 let sum_thread_delta = new_thread_usage.1.saturating_sub(old_thread_usage.1);
                 let work_thread_time = sum_thread_delta.saturating_sub(idle_thread_delta);
                 let curr_threadbox = &imp.thread_graphs.borrow()[i];
-                let thread_fraction =
-                    ((work_thread_time as f64) / (sum_thread_delta as f64)).nan_default(0.0);
+                let thread_fraction = imp.thread_usage_emas.borrow_mut()[i].push(
+                    ((work_thread_time as f64) / (sum_thread_delta as f64)).nan_default(0.0),
+                    alpha,
+                );
 
                 curr_threadbox.push_data_point(thread_fraction);
                 curr_threadbox.set_title_label(&format!("{} %", (thread_fraction * 100.0).round()));
 
-                curr_threadbox.set_subtitle(&convert_frequency(frequencies[i] as f64));
+                curr_threadbox.set_subtitle(
+                    &frequencies
+                        .get(i)
+                        .copied()
+                        .flatten()
+                        .map_or_else(|| i18n("N/A"), |freq| convert_frequency(freq as f64)),
+                );
                 *old_thread_usage = new_thread_usage;
             }
         }
 
+        if let Ok(new_total_breakdown) = new_total_breakdown {
+            let delta = new_total_breakdown.saturating_sub(&imp.old_total_breakdown.get());
+            let total = delta.total() as f64;
+            let percentage = |time: u64| {
+                format!(
+                    "{} %",
+                    ((time as f64 / total).nan_default(0.0) * 100.0).round()
+                )
+            };
+            imp.usage_breakdown.set_subtitle(&i18n_f(
+                "User: {} · System: {} · I/O Wait: {} · Steal: {}",
+                &[
+                    &percentage(delta.user + delta.nice),
+                    &percentage(delta.system + delta.irq + delta.softirq),
+                    &percentage(delta.iowait),
+                    &percentage(delta.steal),
+                ],
+            ));
+            imp.old_total_breakdown.set(*new_total_breakdown);
+        } else {
+            imp.usage_breakdown.set_subtitle(&i18n("N/A"));
+        }
+
         if let Ok(temp) = temperature {
-            imp.temperature
-                .set_subtitle(&convert_temperature(*temp as f64));
+            let formatted_temp = convert_temperature(*temp as f64);
+            if let Some(critical_temperature) = imp.critical_temperature.get() {
+                imp.temperature.set_subtitle(&i18n_f(
+                    "{} (critical at {})",
+                    &[
+                        &formatted_temp,
+                        &convert_temperature(critical_temperature as f64),
+                    ],
+                ));
+            } else {
+                imp.temperature.set_subtitle(&formatted_temp);
+            }
         } else {
             imp.temperature.set_subtitle(&i18n("N/A"));
         }
 
+        self.refresh_throttling(*throttle_counts);
+
         self.set_property("usage", total_fraction);
 
         self.set_property("tab_subtitle", percentage_string);
     }
+
+    /// Shows whether the CPU has been throttled since the last refresh or
+    /// at least since boot, hides the row if that's unknown
+    fn refresh_throttling(&self, throttle_counts: Option<ThrottleCounts>) {
+        let imp = self.imp();
+
+        let Some(throttle_counts) = throttle_counts else {
+            imp.throttling.set_visible(false);
+            return;
+        };
+        imp.throttling.set_visible(true);
+
+        let active = imp
+            .old_throttle_counts
+            .get()
+            .is_some_and(|old_throttle_counts| {
+                throttle_counts.throttled_since(&old_throttle_counts)
+            });
+        imp.throttling_warning.set_visible(active);
+
+        let subtitle = if active {
+            i18n("Currently throttled due to high temperatures")
+        } else if throttle_counts.total() == 0 {
+            i18n("Not throttled since boot")
+        } else {
+            ni18n_f(
+                "Throttled {} time since boot",
+                "Throttled {} times since boot",
+                throttle_counts.total() as u32,
+                &[&throttle_counts.total().to_string()],
+            )
+        };
+        imp.throttling.set_subtitle(&subtitle);
+
+        imp.old_throttle_counts.set(Some(throttle_counts));
+    }
 }
@@ -5,8 +5,9 @@ use gtk::glib;
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
-use crate::utils::drive::{Drive, DriveData};
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::drive::{Drive, DriveActivity, DriveData, STAT_SECTOR_SIZE};
+use crate::utils::settings::SETTINGS;
+use crate::utils::units::{convert_speed, convert_storage, convert_temperature};
 
 mod imp {
     use std::{
@@ -28,6 +29,8 @@ mod imp {
     #[template(resource = "/net/nokyan/Resources/ui/pages/drive.ui")]
     #[properties(wrapper_type = super::ResDrive)]
     pub struct ResDrive {
+        #[template_child]
+        pub near_full_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub total_usage: TemplateChild<ResGraphBox>,
         #[template_child]
@@ -44,6 +47,8 @@ mod imp {
         pub writable: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub removable: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub temperature: TemplateChild<adw::ActionRow>,
         pub old_stats: RefCell<HashMap<String, usize>>,
         pub drive: RefCell<Drive>,
         pub last_timestamp: Cell<SystemTime>,
@@ -57,6 +62,9 @@ mod imp {
         #[property(get, set)]
         usage: Cell<f64>,
 
+        #[property(get, set)]
+        near_full: Cell<bool>,
+
         #[property(get = Self::tab_name, set = Self::set_tab_name, type = glib::GString)]
         tab_name: Cell<glib::GString>,
 
@@ -102,6 +110,7 @@ mod imp {
     impl Default for ResDrive {
         fn default() -> Self {
             Self {
+                near_full_banner: Default::default(),
                 total_usage: Default::default(),
                 read: Default::default(),
                 write: Default::default(),
@@ -110,9 +119,11 @@ mod imp {
                 capacity: Default::default(),
                 writable: Default::default(),
                 removable: Default::default(),
+                temperature: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(Drive::default_icon()),
                 usage: Default::default(),
+                near_full: Default::default(),
                 tab_name: Cell::new(glib::GString::from(i18n("Drive"))),
                 old_stats: Default::default(),
                 drive: Default::default(),
@@ -232,11 +243,14 @@ impl ResDrive {
             removable,
             disk_stats,
             capacity,
+            partitions,
+            temperature,
         } = drive_data;
 
-        let time_passed = SystemTime::now()
+        let elapsed = SystemTime::now()
             .duration_since(imp.last_timestamp.get())
-            .map_or(1.0f64, |timestamp| timestamp.as_secs_f64());
+            .unwrap_or(Duration::from_secs(1));
+        let time_passed = elapsed.as_secs_f64();
 
         if writable {
             imp.writable.set_subtitle(&i18n("Yes"));
@@ -250,6 +264,12 @@ impl ResDrive {
             imp.removable.set_subtitle(&i18n("No"));
         }
 
+        if let Some(temperature) = temperature {
+            imp.temperature
+                .set_subtitle(&convert_temperature(f64::from(temperature)));
+        }
+        imp.temperature.set_visible(temperature.is_some());
+
         let total_usage = if let (
             Some(read_ticks),
             Some(write_ticks),
@@ -276,31 +296,16 @@ impl ResDrive {
         imp.total_usage.set_subtitle(&percentage_string);
         self.set_property("usage", total_usage.unwrap_or(0.0));
 
-        let rw_bytes_per_second = if let (
-            Some(read_sectors),
-            Some(write_sectors),
-            Some(old_read_sectors),
-            Some(old_write_sectors),
-        ) = (
-            disk_stats.get("read_sectors"),
-            disk_stats.get("write_sectors"),
-            imp.old_stats.borrow().get("read_sectors"),
-            imp.old_stats.borrow().get("write_sectors"),
-        ) {
-            let delta_read_sectors = read_sectors.saturating_sub(*old_read_sectors);
-            let delta_write_sectors = write_sectors.saturating_sub(*old_write_sectors);
-            Some((
-                (delta_read_sectors * 512) as f64 / time_passed,
-                (delta_write_sectors * 512) as f64 / time_passed,
-            ))
-        } else {
-            None
-        };
+        let activity = DriveActivity::from_stats(
+            &imp.old_stats.borrow(),
+            &disk_stats,
+            elapsed,
+            STAT_SECTOR_SIZE,
+        )
+        .unwrap_or_default();
 
-        let formatted_read_speed =
-            convert_speed(rw_bytes_per_second.unwrap_or((0.0, 0.0)).0, false);
-        let formatted_write_speed =
-            convert_speed(rw_bytes_per_second.unwrap_or((0.0, 0.0)).1, false);
+        let formatted_read_speed = convert_speed(activity.read_bytes_per_sec, false);
+        let formatted_write_speed = convert_speed(activity.write_bytes_per_sec, false);
 
         imp.read.set_subtitle(&formatted_read_speed);
         imp.write.set_subtitle(&formatted_write_speed);
@@ -318,6 +323,24 @@ impl ResDrive {
         imp.capacity
             .set_subtitle(&convert_storage(capacity as f64, false));
 
+        let threshold = SETTINGS.drive_full_threshold();
+        let near_full_mount_points: Vec<String> = partitions
+            .iter()
+            .filter(|partition| partition.is_near_full(threshold))
+            .map(|partition| partition.mount_point.to_string_lossy().to_string())
+            .collect();
+
+        if near_full_mount_points.is_empty() {
+            imp.near_full_banner.set_revealed(false);
+        } else {
+            imp.near_full_banner.set_title(&i18n_f(
+                "Almost out of space on {}",
+                &[&near_full_mount_points.join(", ")],
+            ));
+            imp.near_full_banner.set_revealed(true);
+        }
+        self.set_near_full(!near_full_mount_points.is_empty());
+
         *imp.old_stats.borrow_mut() = disk_stats;
         imp.last_timestamp.set(SystemTime::now());
     }
@@ -210,6 +210,7 @@ impl ResGPU {
             clock_speed,
             vram_speed,
             temp,
+            temp_junction,
             power_usage,
             power_cap,
             power_cap_max,
@@ -268,8 +269,17 @@ impl ResGPU {
             imp.vram_usage.set_graph_visible(false);
         }
 
-        imp.temperature
-            .set_subtitle(&temp.map_or_else(|| i18n("N/A"), convert_temperature));
+        let temperature_string = match (temp, temp_junction) {
+            (Some(temp), Some(temp_junction)) => i18n_f(
+                "{} (Junction: {})",
+                &[
+                    &convert_temperature(temp),
+                    &convert_temperature(temp_junction),
+                ],
+            ),
+            (temp, _) => temp.map_or_else(|| i18n("N/A"), convert_temperature),
+        };
+        imp.temperature.set_subtitle(&temperature_string);
 
         imp.power_usage
             .set_subtitle(&power_usage.map_or_else(|| i18n("N/A"), convert_power));
@@ -4,6 +4,7 @@ use gtk::glib::{self, clone};
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::utils::memory::{self, MemoryData, MemoryDevice};
+use crate::utils::settings::SETTINGS;
 use crate::utils::units::convert_storage;
 use crate::utils::NaNDefault;
 
@@ -29,6 +30,8 @@ mod imp {
         #[template_child]
         pub swap: TemplateChild<ResGraphBox>,
         #[template_child]
+        pub pressure: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub authentication_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub properties: TemplateChild<adw::PreferencesGroup>,
@@ -91,6 +94,7 @@ mod imp {
                 form_factor: Default::default(),
                 memory_type: Default::default(),
                 type_detail: Default::default(),
+                pressure: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(ThemedIcon::new("memory-symbolic").into()),
                 usage: Default::default(),
@@ -164,6 +168,9 @@ impl ResMemory {
         imp.memory.set_title_label(&i18n("Memory"));
         imp.memory.set_graph_color(129, 61, 156);
         imp.memory.set_data_points_max_amount(60);
+        imp.memory
+            .set_locked_max_y(Self::memory_graph_max_y(SETTINGS.memory_graph_auto_scale()));
+        imp.memory.set_auto_scale_decay(true);
         imp.swap.set_title_label(&i18n("Swap"));
         imp.swap.set_graph_color(46, 194, 126);
         imp.swap.set_data_points_max_amount(60);
@@ -212,6 +219,13 @@ impl ResMemory {
 
     pub fn setup_signals(&self) {
         let imp = self.imp();
+
+        SETTINGS.connect_memory_graph_auto_scale(clone!(@weak self as this => move |auto_scale| {
+            this.imp()
+                .memory
+                .set_locked_max_y(Self::memory_graph_max_y(auto_scale));
+        }));
+
         imp.authentication_banner
             .connect_button_clicked(clone!(@strong self as this => move |_| {
                 let imp = this.imp();
@@ -223,41 +237,68 @@ impl ResMemory {
             }));
     }
 
+    fn memory_graph_max_y(auto_scale: bool) -> Option<f64> {
+        if auto_scale {
+            None
+        } else {
+            Some(1.0)
+        }
+    }
+
     pub fn refresh_page(&self, memdata: MemoryData) {
         let imp = self.imp();
 
+        let used_mem = memdata.used_mem();
+        let used_swap = memdata.used_swap();
+
         let MemoryData {
             total_mem,
             available_mem,
             free_mem: _,
             total_swap,
-            free_swap,
+            free_swap: _,
+            pressure,
         } = memdata;
 
-        let used_mem = total_mem.saturating_sub(available_mem);
-        let used_swap = total_swap.saturating_sub(free_swap);
-
         let memory_fraction = used_mem as f64 / total_mem as f64;
         let swap_fraction = (used_swap as f64 / total_swap as f64).nan_default(0.0);
 
-        let formatted_used_mem = convert_storage(used_mem as f64, false);
         let formatted_total_mem = convert_storage(total_mem as f64, false);
 
+        // the graph always shows the used memory, only the labels change
+        let memory_label = if SETTINGS.memory_show_available() {
+            i18n_f(
+                "{} / {} available",
+                &[
+                    &convert_storage(available_mem as f64, false),
+                    &formatted_total_mem,
+                ],
+            )
+        } else {
+            format!(
+                "{} / {}",
+                &convert_storage(used_mem as f64, false),
+                &formatted_total_mem
+            )
+        };
+
+        let shown_memory_fraction = if SETTINGS.memory_show_available() {
+            1.0 - memory_fraction
+        } else {
+            memory_fraction
+        };
+
         imp.memory.push_data_point(memory_fraction);
         imp.memory.set_subtitle(&format!(
-            "{} / {} · {} %",
-            &formatted_used_mem,
-            &formatted_total_mem,
-            (memory_fraction * 100.0).round()
+            "{} · {} %",
+            &memory_label,
+            (shown_memory_fraction * 100.0).round()
         ));
         if total_swap == 0 {
             imp.swap.push_data_point(0.0);
             imp.swap.set_graph_visible(false);
             imp.swap.set_subtitle(&i18n("N/A"));
-            self.set_property(
-                "tab_subtitle",
-                format!("{} / {}", &formatted_used_mem, &formatted_total_mem),
-            );
+            self.set_property("tab_subtitle", memory_label.as_str());
         } else {
             imp.swap.push_data_point(swap_fraction);
             imp.swap.set_graph_visible(true);
@@ -272,16 +313,24 @@ impl ResMemory {
                 i18n_f(
                     // Translators: This will be displayed in the sidebar, so your translation for "Swap" should
                     // preferably be quite short or an abbreviation
-                    "{} / {} · Swap: {} %",
-                    &[
-                        &formatted_used_mem,
-                        &formatted_total_mem,
-                        &(swap_fraction * 100.0).round().to_string(),
-                    ],
+                    "{} · Swap: {} %",
+                    &[&memory_label, &(swap_fraction * 100.0).round().to_string()],
                 ),
             );
         }
 
+        if let Some(pressure) = pressure {
+            let some = format!("{:.1} %", pressure.some.avg10);
+            let full = pressure
+                .full
+                .map_or_else(|| i18n("N/A"), |full| format!("{:.1} %", full.avg10));
+            imp.pressure
+                .set_subtitle(&i18n_f("Some: {} · Full: {}", &[&some, &full]));
+            imp.pressure.set_visible(true);
+        } else {
+            imp.pressure.set_visible(false);
+        }
+
         self.set_property("usage", memory_fraction);
     }
 }
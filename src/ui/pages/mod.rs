@@ -1,4 +1,5 @@
 pub mod applications;
+pub mod context_menu;
 pub mod cpu;
 pub mod drive;
 pub mod gpu;
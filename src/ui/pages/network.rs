@@ -5,7 +5,7 @@ use gtk::glib;
 
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
-use crate::utils::network::{InterfaceType, NetworkData, NetworkInterface};
+use crate::utils::network::{self, InterfaceType, NetworkData, NetworkInterface};
 use crate::utils::units::{convert_speed, convert_storage};
 use crate::utils::NaNDefault;
 
@@ -42,6 +42,8 @@ mod imp {
         pub interface: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub hw_address: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub addresses: TemplateChild<adw::ActionRow>,
         pub old_received_bytes: Cell<usize>,
         pub old_sent_bytes: Cell<usize>,
         pub last_timestamp: Cell<SystemTime>,
@@ -109,6 +111,7 @@ mod imp {
                 driver: Default::default(),
                 interface: Default::default(),
                 hw_address: Default::default(),
+                addresses: Default::default(),
                 uses_progress_bar: Cell::new(true),
                 icon: RefCell::new(ThemedIcon::new("unknown-network-type-symbolic").into()),
                 usage: Default::default(),
@@ -259,6 +262,7 @@ impl ResNetwork {
         let NetworkData {
             received_bytes,
             sent_bytes,
+            addresses,
 
             inner: _,
             is_virtual: _,
@@ -274,6 +278,23 @@ impl ResNetwork {
             (received_bytes.saturating_sub(imp.old_received_bytes.get())) as f64 / time_passed;
         let sent_delta = (sent_bytes.saturating_sub(imp.old_sent_bytes.get())) as f64 / time_passed;
 
+        if addresses.is_empty() {
+            imp.addresses.set_subtitle(&i18n("N/A"));
+        } else {
+            let addresses = addresses
+                .iter()
+                .map(|address| {
+                    if network::is_link_local(address) {
+                        i18n_f("{} (link-local)", &[&address.to_string()])
+                    } else {
+                        address.to_string()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n");
+            imp.addresses.set_subtitle(&addresses);
+        }
+
         imp.total_received
             .set_subtitle(&convert_storage(received_bytes as f64, false));
         imp.total_sent
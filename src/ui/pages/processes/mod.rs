@@ -6,7 +6,7 @@ use std::collections::HashSet;
 use adw::ResponseAppearance;
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::glib::{self, clone, closure, Object, Sender};
-use gtk::{gio, CustomSorter, FilterChange, Ordering, SortType, Widget};
+use gtk::{gio, ClosureExpression, CustomSorter, FilterChange, Ordering, SortType, Widget};
 use gtk_macros::send;
 
 use log::error;
@@ -14,18 +14,19 @@ use log::error;
 use crate::config::PROFILE;
 use crate::i18n::{i18n, i18n_f};
 use crate::ui::dialogs::process_dialog::ResProcessDialog;
+use crate::ui::pages::context_menu::{add_context_menu_gesture, new_context_menu, ContextMenuPage};
 use crate::ui::window::{self, Action, MainWindow};
 use crate::utils::app::AppsContext;
-use crate::utils::process::{ProcessAction, ProcessItem};
+use crate::utils::process::{ProcessAction, ProcessItem, ReverseLookup};
 use crate::utils::settings::SETTINGS;
-use crate::utils::units::{convert_speed, convert_storage};
+use crate::utils::units::{convert_cpu_usage, convert_speed, convert_storage};
 
 use self::process_entry::ProcessEntry;
 use self::process_name_cell::ResProcessNameCell;
 
 mod imp {
     use std::{
-        cell::{Cell, RefCell},
+        cell::{Cell, OnceCell, RefCell},
         collections::HashMap,
         sync::OnceLock,
     };
@@ -53,6 +54,8 @@ mod imp {
         #[template_child]
         pub processes_scrolled_window: TemplateChild<gtk::ScrolledWindow>,
         #[template_child]
+        pub process_context_menu: TemplateChild<gio::MenuModel>,
+        #[template_child]
         pub search_button: TemplateChild<gtk::ToggleButton>,
         #[template_child]
         pub information_button: TemplateChild<gtk::Button>,
@@ -64,10 +67,17 @@ mod imp {
         pub filter_model: RefCell<gtk::FilterListModel>,
         pub sort_model: RefCell<gtk::SortListModel>,
         pub column_view: RefCell<gtk::ColumnView>,
+        pub context_menu: OnceCell<gtk::PopoverMenu>,
         pub open_dialog: RefCell<Option<(i32, ResProcessDialog)>>,
 
         pub username_cache: RefCell<HashMap<u32, String>>,
 
+        /// The current search if it's a port or file search
+        pub reverse_lookup: RefCell<Option<ReverseLookup>>,
+        /// PIDs of the processes matching `reverse_lookup`, `None` while
+        /// they're still being looked up
+        pub reverse_lookup_pids: RefCell<Option<HashSet<i32>>>,
+
         pub sender: OnceLock<Sender<Action>>,
 
         #[property(get)]
@@ -118,8 +128,12 @@ mod imp {
                 filter_model: Default::default(),
                 sort_model: Default::default(),
                 column_view: Default::default(),
+                process_context_menu: Default::default(),
+                context_menu: Default::default(),
                 open_dialog: Default::default(),
                 username_cache: Default::default(),
+                reverse_lookup: Default::default(),
+                reverse_lookup_pids: Default::default(),
                 sender: Default::default(),
                 uses_progress_bar: Cell::new(false),
                 icon: RefCell::new(ThemedIcon::new("generic-process-symbolic").into()),
@@ -166,6 +180,22 @@ mod imp {
                 },
             );
 
+            klass.install_action("processes.copy-pid", None, move |res_processes, _, _| {
+                if let Some(process) = res_processes.get_selected_process_item() {
+                    res_processes.copy_to_clipboard(&process.pid.to_string());
+                }
+            });
+
+            klass.install_action(
+                "processes.copy-commandline",
+                None,
+                move |res_processes, _, _| {
+                    if let Some(process) = res_processes.get_selected_process_item() {
+                        res_processes.copy_to_clipboard(&process.commandline);
+                    }
+                },
+            );
+
             Self::bind_template(klass);
         }
 
@@ -258,6 +288,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = ResProcessNameCell::new();
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("name")
                 .bind(&row, "name", Widget::NONE);
@@ -268,6 +299,18 @@ impl ResProcesses {
                 .chain_property::<ProcessEntry>("commandline")
                 .bind(&row, "tooltip", Widget::NONE);
         });
+        name_col_factory.connect_bind(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = item.child().and_downcast::<ResProcessNameCell>().unwrap();
+            if let Some(process_item) = item
+                .item()
+                .and_downcast::<ProcessEntry>()
+                .unwrap()
+                .process_item()
+            {
+                row.set_trust_level(process_item.trust_level());
+            }
+        });
         let name_col_sorter = CustomSorter::new(move |a, b| {
             let item_a = a.downcast_ref::<ProcessEntry>().unwrap();
             let item_b = b.downcast_ref::<ProcessEntry>().unwrap();
@@ -287,6 +330,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("pid")
                 .bind(&row, "text", Widget::NONE);
@@ -307,6 +351,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("user")
                 .bind(&row, "text", Widget::NONE);
@@ -328,6 +373,7 @@ impl ResProcesses {
             let row = gtk::Inscription::new(None);
             row.set_min_chars(9);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("memory_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, memory_usage: u64| {
@@ -351,12 +397,37 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("cpu_usage")
                 .chain_closure::<String>(closure!(|_: Option<Object>, cpu_usage: f32| {
-                    format!("{:.1} %", cpu_usage * 100.0)
+                    convert_cpu_usage(cpu_usage)
                 }))
                 .bind(&row, "text", Widget::NONE);
+            let item_expression = item.property_expression("item");
+            ClosureExpression::new::<String>(
+                [
+                    item_expression.chain_property::<ProcessEntry>("user_cpu_usage"),
+                    item_expression.chain_property::<ProcessEntry>("system_cpu_usage"),
+                ],
+                closure!(
+                    |_: Option<Object>, user_cpu_usage: f32, system_cpu_usage: f32| {
+                        let cpu_usage = user_cpu_usage + system_cpu_usage;
+                        if cpu_usage == 0.0 {
+                            // an empty tooltip is not shown at all
+                            return String::new();
+                        }
+                        i18n_f(
+                            "User: {} % · System: {} %",
+                            &[
+                                &((user_cpu_usage / cpu_usage) * 100.0).round().to_string(),
+                                &((system_cpu_usage / cpu_usage) * 100.0).round().to_string(),
+                            ],
+                        )
+                    }
+                ),
+            )
+            .bind(&row, "tooltip-text", Widget::NONE);
         });
         let cpu_col_sorter = CustomSorter::new(move |a, b| {
             let item_a = a.downcast_ref::<ProcessEntry>().unwrap().cpu_usage();
@@ -382,6 +453,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("read_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_speed: f64| {
@@ -417,6 +489,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("read_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, read_total: i64| {
@@ -446,6 +519,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("write_speed")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_speed: f64| {
@@ -481,6 +555,7 @@ impl ResProcesses {
             let item = item.downcast_ref::<gtk::ListItem>().unwrap();
             let row = gtk::Inscription::new(None);
             item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
             item.property_expression("item")
                 .chain_property::<ProcessEntry>("write_total")
                 .chain_closure::<String>(closure!(|_: Option<Object>, write_total: i64| {
@@ -500,6 +575,29 @@ impl ResProcesses {
         write_total_col.set_sorter(Some(&write_total_col_sorter));
         write_total_col.set_visible(SETTINGS.processes_show_drive_write_total());
 
+        let cgroup_kind_col_factory = gtk::SignalListItemFactory::new();
+        let cgroup_kind_col = gtk::ColumnViewColumn::new(
+            Some(&i18n("Control Group")),
+            Some(cgroup_kind_col_factory.clone()),
+        );
+        cgroup_kind_col.set_resizable(true);
+        cgroup_kind_col_factory.connect_setup(move |_factory, item| {
+            let item = item.downcast_ref::<gtk::ListItem>().unwrap();
+            let row = gtk::Inscription::new(None);
+            item.set_child(Some(&row));
+            add_context_menu_gesture::<ResProcesses>(item, &row);
+            item.property_expression("item")
+                .chain_property::<ProcessEntry>("cgroup_kind")
+                .bind(&row, "text", Widget::NONE);
+        });
+        let cgroup_kind_col_sorter = CustomSorter::new(move |a, b| {
+            let item_a = a.downcast_ref::<ProcessEntry>().unwrap();
+            let item_b = b.downcast_ref::<ProcessEntry>().unwrap();
+            item_a.cgroup_kind().cmp(&item_b.cgroup_kind()).into()
+        });
+        cgroup_kind_col.set_sorter(Some(&cgroup_kind_col_sorter));
+        cgroup_kind_col.set_visible(SETTINGS.processes_show_cgroup());
+
         column_view.append_column(&name_col);
         column_view.append_column(&pid_col);
         column_view.append_column(&user_col);
@@ -509,6 +607,7 @@ impl ResProcesses {
         column_view.append_column(&read_total_col);
         column_view.append_column(&write_speed_col);
         column_view.append_column(&write_total_col);
+        column_view.append_column(&cgroup_kind_col);
 
         column_view.sort_by_column(Some(&memory_col), SortType::Descending);
 
@@ -528,6 +627,22 @@ impl ResProcesses {
         SETTINGS.connect_processes_show_drive_write_total(move |visible| {
             write_total_col.set_visible(visible)
         });
+        SETTINGS.connect_processes_show_cgroup(move |visible| cgroup_kind_col.set_visible(visible));
+
+        if SETTINGS.color_code_trust() {
+            column_view.add_css_class("trust-colors");
+        }
+        SETTINGS.connect_color_code_trust(clone!(@weak column_view => move |enabled| {
+            if enabled {
+                column_view.add_css_class("trust-colors");
+            } else {
+                column_view.remove_css_class("trust-colors");
+            }
+        }));
+
+        let _ = imp
+            .context_menu
+            .set(new_context_menu(&imp.process_context_menu, &column_view));
 
         column_view.set_enable_rubberband(true);
         imp.processes_scrolled_window.set_child(Some(&column_view));
@@ -560,26 +675,29 @@ impl ResProcesses {
         imp.search_entry
             .connect_search_changed(clone!(@strong self as this => move |_| {
                 let imp = this.imp();
+                let reverse_lookup = ReverseLookup::from_search(&imp.search_entry.text());
+                if *imp.reverse_lookup.borrow() != reverse_lookup {
+                    *imp.reverse_lookup_pids.borrow_mut() = None;
+                    *imp.reverse_lookup.borrow_mut() = reverse_lookup.clone();
+                    if let Some(reverse_lookup) = reverse_lookup {
+                        this.spawn_reverse_lookup(reverse_lookup);
+                    }
+                }
                 if let Some(filter) = imp.filter_model.borrow().filter() {
                     filter.changed(FilterChange::Different);
                 }
             }));
 
         imp.information_button
-        .connect_clicked(clone!(@strong self as this => move |_| {
-            let imp = this.imp();
-                let selection_option = imp.selection_model.borrow()
-                .selected_item()
-                .map(|object| {
-                    object
-                    .downcast::<ProcessEntry>()
-                    .unwrap()
-                });
+            .connect_clicked(clone!(@strong self as this => move |_| {
+                let imp = this.imp();
+                let selection_option = imp
+                    .selection_model
+                    .borrow()
+                    .selected_item()
+                    .map(|object| object.downcast::<ProcessEntry>().unwrap());
                 if let Some(selection) = selection_option {
-                    let process_dialog = ResProcessDialog::new();
-                    process_dialog.init(selection.process_item().as_ref().unwrap(), selection.user());
-                    process_dialog.show();
-                    *imp.open_dialog.borrow_mut() = Some((selection.pid(), process_dialog));
+                    this.show_process_dialog(&selection);
                 }
             }));
 
@@ -591,15 +709,105 @@ impl ResProcesses {
             }));
     }
 
+    fn show_process_dialog(&self, process_entry: &ProcessEntry) {
+        let process_dialog = ResProcessDialog::new();
+        process_dialog.init(
+            process_entry.process_item().as_ref().unwrap(),
+            process_entry.user(),
+        );
+        process_dialog.show();
+        *self.imp().open_dialog.borrow_mut() = Some((process_entry.pid(), process_dialog));
+    }
+
+    /// Opens the information dialog of the process with the ID `pid` or shows
+    /// a toast if there is no such process
+    pub fn open_process_dialog(&self, pid: i32) {
+        let imp = self.imp();
+
+        let process_entry = imp
+            .store
+            .borrow()
+            .iter::<ProcessEntry>()
+            .flatten()
+            .find(|process_entry| process_entry.pid() == pid);
+
+        if let Some(process_entry) = process_entry {
+            self.show_process_dialog(&process_entry);
+        } else {
+            error!("Unable to find process with ID {pid}");
+            imp.toast_overlay.add_toast(adw::Toast::new(&i18n_f(
+                "There is no process with the ID {}",
+                &[&pid.to_string()],
+            )));
+        }
+    }
+
+    fn copy_to_clipboard(&self, text: &str) {
+        self.clipboard().set_text(text);
+        self.imp()
+            .toast_overlay
+            .add_toast(adw::Toast::new(&i18n("Copied to clipboard")));
+    }
+
     fn search_filter(&self, obj: &Object) -> bool {
         let imp = self.imp();
         let item = obj.downcast_ref::<ProcessEntry>().unwrap();
+        if !imp.search_revealer.reveals_child() {
+            return true;
+        }
+        if imp.reverse_lookup.borrow().is_some() {
+            return imp
+                .reverse_lookup_pids
+                .borrow()
+                .as_ref()
+                .is_some_and(|pids| pids.contains(&item.pid()));
+        }
         let search_string = imp.search_entry.text().to_string().to_lowercase();
-        !imp.search_revealer.reveals_child()
-            || item.name().to_lowercase().contains(&search_string)
+        item.name().to_lowercase().contains(&search_string)
             || item.commandline().to_lowercase().contains(&search_string)
     }
 
+    /// Looks up the processes matching `reverse_lookup` on a blocking thread
+    /// right after the search changed, afterwards the refresh task looks them
+    /// up again on every refresh
+    fn spawn_reverse_lookup(&self, reverse_lookup: ReverseLookup) {
+        let main_context = glib::MainContext::default();
+        main_context.spawn_local(clone!(@weak self as this => async move {
+            let lookup = reverse_lookup.clone();
+            if let Ok(pids) = tokio::task::spawn_blocking(move || lookup.pids()).await {
+                this.refresh_reverse_lookup_pids(&reverse_lookup, pids);
+            }
+        }));
+    }
+
+    /// Returns the current search if it's a port or file search, the refresh
+    /// task looks up its processes
+    pub fn reverse_lookup(&self) -> Option<ReverseLookup> {
+        let imp = self.imp();
+        if imp.search_revealer.reveals_child() {
+            imp.reverse_lookup.borrow().clone()
+        } else {
+            None
+        }
+    }
+
+    /// Shows the processes found for `reverse_lookup`, unless the search has
+    /// changed since they were looked up
+    pub fn refresh_reverse_lookup_pids(
+        &self,
+        reverse_lookup: &ReverseLookup,
+        pids: impl IntoIterator<Item = i32>,
+    ) {
+        let imp = self.imp();
+        if imp.reverse_lookup.borrow().as_ref() != Some(reverse_lookup) {
+            return;
+        }
+        *imp.reverse_lookup_pids.borrow_mut() = Some(pids.into_iter().collect());
+        if let Some(filter) = imp.filter_model.borrow().filter() {
+            filter.changed(FilterChange::Different);
+        }
+    }
+
     fn get_selected_process_item(&self) -> Option<ProcessItem> {
         self.imp()
             .selection_model
@@ -608,6 +816,21 @@ impl ResProcesses {
             .and_then(|object| object.downcast::<ProcessEntry>().unwrap().process_item())
     }
 
+    /// Returns the currently listed processes along with the names of their users
+    pub fn process_items(&self) -> Vec<(ProcessItem, String)> {
+        self.imp()
+            .store
+            .borrow()
+            .iter::<ProcessEntry>()
+            .flatten()
+            .filter_map(|entry| {
+                entry
+                    .process_item()
+                    .map(|process_item| (process_item, entry.user().to_string()))
+            })
+            .collect()
+    }
+
     pub fn refresh_processes_list(&self, apps: &AppsContext) {
         let imp = self.imp();
 
@@ -662,8 +885,7 @@ impl ResProcesses {
     pub fn execute_process_action_dialog(&self, process: ProcessItem, action: ProcessAction) {
         let imp = self.imp();
 
-        // Nothing too bad can happen on Continue so dont show the dialog
-        if action == ProcessAction::CONT {
+        if !window::needs_confirmation(action) {
             send!(
                 imp.sender.get().unwrap(),
                 Action::ManipulateProcess(
@@ -730,3 +952,17 @@ impl ResProcesses {
         .to_string()
     }
 }
+
+impl ContextMenuPage for ResProcesses {
+    fn context_menu(&self) -> Option<gtk::PopoverMenu> {
+        self.imp().context_menu.get().cloned()
+    }
+
+    fn column_view(&self) -> gtk::ColumnView {
+        self.imp().column_view.borrow().clone()
+    }
+
+    fn select_for_context_menu(&self, position: u32) {
+        self.imp().selection_model.borrow().set_selected(position);
+    }
+}
@@ -29,6 +29,10 @@ mod imp {
         #[property(get = Self::user, set = Self::set_user, type = glib::GString)]
         user: Cell<glib::GString>,
 
+        /// See `ProcessItem::cgroup_kind_name()`, empty if it's `None`
+        #[property(get = Self::cgroup_kind, set = Self::set_cgroup_kind, type = glib::GString)]
+        cgroup_kind: Cell<glib::GString>,
+
         #[property(get = Self::icon, set = Self::set_icon, type = Icon)]
         icon: RefCell<Icon>,
 
@@ -38,6 +42,12 @@ mod imp {
         #[property(get, set)]
         cpu_usage: Cell<f32>,
 
+        #[property(get, set)]
+        user_cpu_usage: Cell<f32>,
+
+        #[property(get, set)]
+        system_cpu_usage: Cell<f32>,
+
         #[property(get, set)]
         memory_usage: Cell<u64>,
 
@@ -62,9 +72,12 @@ mod imp {
                 name: Cell::new(glib::GString::default()),
                 commandline: Cell::new(glib::GString::default()),
                 user: Cell::new(glib::GString::default()),
+                cgroup_kind: Cell::new(glib::GString::default()),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
                 pid: Cell::new(0),
                 cpu_usage: Cell::new(0.0),
+                user_cpu_usage: Cell::new(0.0),
+                system_cpu_usage: Cell::new(0.0),
                 memory_usage: Cell::new(0),
                 process_item: RefCell::new(None),
                 read_speed: Cell::new(0.0),
@@ -109,6 +122,17 @@ mod imp {
             self.user.set(glib::GString::from(user));
         }
 
+        pub fn cgroup_kind(&self) -> glib::GString {
+            let cgroup_kind = self.cgroup_kind.take();
+            let result = cgroup_kind.clone();
+            self.cgroup_kind.set(cgroup_kind);
+            result
+        }
+
+        pub fn set_cgroup_kind(&self, cgroup_kind: &str) {
+            self.cgroup_kind.set(glib::GString::from(cgroup_kind));
+        }
+
         pub fn icon(&self) -> Icon {
             let icon = self
                 .icon
@@ -158,6 +182,10 @@ impl ProcessEntry {
             .property("name", &process_item.display_name)
             .property("commandline", &process_item.commandline)
             .property("user", user)
+            .property(
+                "cgroup_kind",
+                process_item.cgroup_kind_name().unwrap_or_default(),
+            )
             .property("icon", &process_item.icon)
             .property("pid", process_item.pid)
             .build();
@@ -167,6 +195,8 @@ impl ProcessEntry {
 
     pub fn update(&self, process_item: ProcessItem) {
         self.set_cpu_usage(process_item.cpu_time_ratio);
+        self.set_user_cpu_usage(process_item.user_cpu_ratio);
+        self.set_system_cpu_usage(process_item.system_cpu_ratio);
         self.set_memory_usage(process_item.memory_usage as u64);
         self.set_read_speed(process_item.read_speed.unwrap_or(-1.0));
         self.set_read_total(
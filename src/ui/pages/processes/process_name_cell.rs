@@ -1,6 +1,8 @@
 use adw::{prelude::*, subclass::prelude::*};
 use gtk::{gio::Icon, glib};
 
+use crate::utils::process::TrustLevel;
+
 mod imp {
     use std::cell::{Cell, RefCell};
 
@@ -149,4 +151,14 @@ impl ResProcessNameCell {
     pub fn new() -> Self {
         glib::Object::new::<Self>()
     }
+
+    pub fn set_trust_level(&self, trust_level: TrustLevel) {
+        self.remove_css_class("trust-sandboxed");
+        self.remove_css_class("trust-elevated");
+        match trust_level {
+            TrustLevel::Sandboxed => self.add_css_class("trust-sandboxed"),
+            TrustLevel::Normal => {}
+            TrustLevel::Elevated => self.add_css_class("trust-elevated"),
+        }
+    }
 }
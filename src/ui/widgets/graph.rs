@@ -5,8 +5,16 @@ use plotters::style::RGBColor;
 
 use std::f64;
 
+/// Factor by which the maximum of an auto-scaled graph shrinks per data point
+/// once its peak has left the graph, so that it doesn't suddenly jump
+const AUTO_SCALE_DECAY: f64 = 0.9;
+
 mod imp {
-    use std::{cell::RefCell, collections::VecDeque, error::Error};
+    use std::{
+        cell::{Cell, RefCell},
+        collections::VecDeque,
+        error::Error,
+    };
 
     use gtk::{
         glib,
@@ -28,6 +36,8 @@ mod imp {
         pub data_points: RefCell<VecDeque<f64>>,
         pub data_points_max_amount: RefCell<usize>,
         pub max_y: RefCell<Option<f64>>,
+        pub auto_max_y: Cell<f64>,
+        pub auto_scale_decay: Cell<bool>,
         pub graph_color: RefCell<RGBColor>,
     }
 
@@ -37,6 +47,8 @@ mod imp {
                 data_points: RefCell::default(),
                 data_points_max_amount: RefCell::default(),
                 max_y: RefCell::new(Some(1.0)),
+                auto_max_y: Cell::new(0.0),
+                auto_scale_decay: Cell::new(false),
                 graph_color: RefCell::default(),
             }
         }
@@ -88,12 +100,7 @@ mod imp {
                 filled_data_points.push(*i);
             }
 
-            let y_max = self.max_y.borrow().unwrap_or_else(|| {
-                *filled_data_points
-                    .iter()
-                    .max_by(|x, y| x.total_cmp(y))
-                    .unwrap()
-            });
+            let y_max = self.max_y.borrow().unwrap_or_else(|| self.auto_max_y.get());
 
             let mut chart = ChartBuilder::on(&root)
                 .build_cartesian_2d(0f64..(*data_points_max_amount as f64 - 1.0), 0f64..y_max)?;
@@ -142,6 +149,13 @@ impl ResGraph {
         imp.obj().queue_draw();
     }
 
+    /// Lets the maximum of the auto-scaled graph shrink slowly instead of
+    /// following the highest visible data point right away
+    pub fn set_auto_scale_decay(&self, auto_scale_decay: bool) {
+        let imp = self.imp();
+        imp.auto_scale_decay.set(auto_scale_decay);
+    }
+
     pub fn get_highest_value(&self) -> f64 {
         let imp = self.imp();
         *imp.data_points
@@ -158,6 +172,17 @@ impl ResGraph {
             data_points.pop_front();
         }
         data_points.push_back(data);
+        drop(data_points);
+
+        // never cut off any data points but decay slowly once the peak has left the graph
+        let highest_value = self.get_highest_value();
+        if imp.auto_scale_decay.get() {
+            imp.auto_max_y
+                .set(highest_value.max(imp.auto_max_y.get() * AUTO_SCALE_DECAY));
+        } else {
+            imp.auto_max_y.set(highest_value);
+        }
+
         imp.obj().queue_draw();
     }
 }
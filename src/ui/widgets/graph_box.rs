@@ -111,6 +111,11 @@ impl ResGraphBox {
         imp.graph.set_locked_max_y(y_max);
     }
 
+    pub fn set_auto_scale_decay(&self, auto_scale_decay: bool) {
+        let imp = self.imp();
+        imp.graph.set_auto_scale_decay(auto_scale_decay);
+    }
+
     pub fn get_highest_value(&self) -> f64 {
         let imp = self.imp();
         imp.graph.get_highest_value()
@@ -152,6 +152,13 @@ impl ResStackSidebar {
                     .build();
             }
 
+            if child.find_property("near_full").is_some() {
+                child
+                    .bind_property("near_full", &sidebar_item, "warning")
+                    .sync_create()
+                    .build();
+            }
+
             let row = gtk::ListBoxRow::builder()
                 .child(&sidebar_item)
                 .selectable(true)
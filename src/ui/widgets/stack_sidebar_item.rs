@@ -27,6 +27,8 @@ mod imp {
         pub subtitle_label: TemplateChild<gtk::Label>,
         #[template_child]
         pub progress_bar: TemplateChild<gtk::ProgressBar>,
+        #[template_child]
+        pub warning_image: TemplateChild<gtk::Image>,
 
         #[property(get = Self::name, set = Self::set_name, type = glib::GString)]
         name: Cell<glib::GString>,
@@ -36,6 +38,8 @@ mod imp {
         icon: RefCell<Icon>,
         #[property(get, set = Self::set_usage)]
         usage: Cell<f64>,
+        #[property(get, set = Self::set_warning)]
+        warning: Cell<bool>,
     }
 
     impl ResStackSidebarItem {
@@ -93,6 +97,11 @@ mod imp {
             self.usage.set(usage);
             self.progress_bar.set_fraction(usage);
         }
+
+        pub fn set_warning(&self, warning: bool) {
+            self.warning.set(warning);
+            self.warning_image.set_visible(warning);
+        }
     }
 
     impl Default for ResStackSidebarItem {
@@ -102,10 +111,12 @@ mod imp {
                 label: Default::default(),
                 progress_bar: Default::default(),
                 subtitle_label: Default::default(),
+                warning_image: Default::default(),
                 name: Default::default(),
                 subtitle: Default::default(),
                 icon: RefCell::new(ThemedIcon::new("generic-process").into()),
                 usage: Default::default(),
+                warning: Default::default(),
             }
         }
     }
@@ -1,13 +1,16 @@
-use hashbrown::HashMap;
-use process_data::ProcessData;
+use hashbrown::{HashMap, HashSet};
+use nix::errno::Errno;
+use process_data::{AllProcessData, READ_TIMEOUT};
 use std::path::PathBuf;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+use tokio::sync::Notify;
 
 use adw::{prelude::*, subclass::prelude::*};
 use adw::{Toast, ToastOverlay};
-use anyhow::Result;
+use anyhow::{Context, Result};
 use gtk::glib::{clone, timeout_future, MainContext};
 use gtk::{gio, glib, Widget};
+use gtk_macros::send;
 
 use crate::application::Application;
 use crate::config::PROFILE;
@@ -17,24 +20,40 @@ use crate::ui::pages::drive::ResDrive;
 use crate::ui::pages::processes::ResProcesses;
 use crate::utils::app::AppsContext;
 use crate::utils::cpu::CpuData;
-use crate::utils::drive::{Drive, DriveData};
+use crate::utils::drive::{Drive, DriveActivity, DriveData, DriveError, STAT_SECTOR_SIZE};
+use crate::utils::export::{self, ExportFormat};
 use crate::utils::gpu::{GpuData, GPU};
 use crate::utils::memory::MemoryData;
 use crate::utils::network::{NetworkData, NetworkInterface};
-use crate::utils::process::{Process, ProcessAction};
+use crate::utils::process::{is_permission_error, Process, ProcessAction, ReverseLookup};
 use crate::utils::settings::SETTINGS;
+use crate::utils::snapshot;
+use crate::utils::units::convert_speed;
 
 use super::pages::gpu::ResGPU;
 use super::pages::network::ResNetwork;
 
+/// The stats of a drive as returned by `Drive::sys_stats()`
+type DiskStats = std::collections::HashMap<String, usize>;
+
 #[derive(Debug, Clone)]
 pub enum Action {
     ManipulateProcess(ProcessAction, i32, String, ToastOverlay),
     ManipulateApp(ProcessAction, String, ToastOverlay),
+    /// Retries an action on the given PIDs with elevated privileges, the
+    /// `String` is the display name of what they belong to
+    ManipulateProcessesElevated(ProcessAction, Vec<i32>, String, ToastOverlay),
+    /// Applies an action to all apps with the given IDs and shows a single
+    /// summary
+    ManipulateApps(ProcessAction, Vec<String>, ToastOverlay),
+    ReniceApp(i32, String, ToastOverlay),
+    /// Sends SIGTERM to an app and SIGKILL to everything of it that's still
+    /// running after the grace period
+    EndAppForcefully(String, ToastOverlay),
 }
 
 mod imp {
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     use crate::{
         ui::{
@@ -57,6 +76,10 @@ mod imp {
     #[derive(Debug, CompositeTemplate)]
     #[template(resource = "/net/nokyan/Resources/ui/window.ui")]
     pub struct MainWindow {
+        #[template_child]
+        pub stats_error_banner: TemplateChild<adw::Banner>,
+        #[template_child]
+        pub paused_banner: TemplateChild<adw::Banner>,
         #[template_child]
         pub split_view: TemplateChild<adw::OverlaySplitView>,
         #[template_child]
@@ -81,6 +104,8 @@ mod imp {
         pub memory: TemplateChild<ResMemory>,
         #[template_child]
         pub memory_page: TemplateChild<gtk::StackPage>,
+        #[template_child]
+        pub disk_activity: TemplateChild<gtk::Label>,
 
         pub drive_pages: RefCell<HashMap<PathBuf, adw::ToolbarView>>,
 
@@ -88,8 +113,19 @@ mod imp {
 
         pub gpu_pages: RefCell<Vec<adw::ToolbarView>>,
 
+        /// The stats of all drives at the last refresh and when they were
+        /// read, used for the total disk activity
+        pub last_disk_stats: RefCell<(HashMap<PathBuf, DiskStats>, Option<Instant>)>,
+
         pub apps_context: RefCell<AppsContext>,
 
+        pub pid_to_open: Cell<Option<i32>>,
+
+        /// Whether the displayed values are currently kept as they are
+        pub refresh_paused: Cell<bool>,
+        /// Cuts the wait for the next refresh short
+        pub refresh_now: Notify,
+
         pub sender: Sender<Action>,
         pub receiver: RefCell<Option<Receiver<Action>>>,
     }
@@ -102,6 +138,8 @@ mod imp {
             Self {
                 drive_pages: RefCell::default(),
                 network_pages: RefCell::default(),
+                stats_error_banner: TemplateChild::default(),
+                paused_banner: TemplateChild::default(),
                 split_view: TemplateChild::default(),
                 resources_sidebar: TemplateChild::default(),
                 content_stack: TemplateChild::default(),
@@ -113,7 +151,12 @@ mod imp {
                 cpu_page: TemplateChild::default(),
                 memory: TemplateChild::default(),
                 memory_page: TemplateChild::default(),
+                disk_activity: TemplateChild::default(),
+                last_disk_stats: RefCell::default(),
                 apps_context: Default::default(),
+                pid_to_open: Cell::default(),
+                refresh_paused: Cell::default(),
+                refresh_now: Notify::default(),
                 sender,
                 receiver,
                 processor_window_title: TemplateChild::default(),
@@ -181,12 +224,29 @@ glib::wrapper! {
 struct RefreshData {
     cpu_data: CpuData,
     mem_data: MemoryData,
-    gpu_data: Vec<GpuData>,
+    gpu_data: Vec<Option<GpuData>>,
     drive_paths: Vec<PathBuf>,
     drive_data: Vec<DriveData>,
     network_paths: Vec<PathBuf>,
     network_data: Vec<NetworkData>,
-    process_data: Vec<ProcessData>,
+    process_data: Result<AllProcessData>,
+    remote_addresses: Option<Vec<String>>,
+    reverse_lookup_pids: Option<(ReverseLookup, HashSet<i32>)>,
+}
+
+/// Data that is too expensive to gather on every refresh and that the UI
+/// asks the refresh task for only while it's shown
+#[derive(Debug, Clone, Default)]
+struct RefreshRequest {
+    /// PIDs of the app whose dialog is open, their remote addresses are
+    /// gathered
+    remote_address_pids: Option<Vec<i32>>,
+    /// The port or file search of the processes page, the processes matching
+    /// it are looked up
+    reverse_lookup: Option<ReverseLookup>,
+    /// Whether the open file descriptors of all processes are counted, which
+    /// needs reading a directory per process
+    count_open_fds: bool,
 }
 
 impl MainWindow {
@@ -206,6 +266,133 @@ impl MainWindow {
         window
     }
 
+    /// Opens the information dialog of the process with the ID `pid` as
+    /// soon as the processes have been gathered for the first time
+    pub fn open_process_dialog_on_start(&self, pid: i32) {
+        self.imp().pid_to_open.set(Some(pid));
+    }
+
+    /// Asks the user for a location and saves a JSON snapshot of the
+    /// current state of the system there
+    pub fn save_snapshot(&self) {
+        let file_chooser = gtk::FileChooserNative::builder()
+            .title(i18n("Save Snapshot"))
+            .transient_for(self)
+            .modal(true)
+            .action(gtk::FileChooserAction::Save)
+            .accept_label(i18n("Save"))
+            .build();
+        file_chooser.set_current_name("resources-snapshot.json");
+
+        file_chooser.connect_response(clone!(@weak self as this => move |file_chooser, response| {
+            if response == gtk::ResponseType::Accept {
+                if let Some(path) = file_chooser.file().and_then(|file| file.path()) {
+                    this.write_snapshot(path);
+                }
+            }
+            file_chooser.destroy();
+        }));
+
+        file_chooser.show();
+    }
+
+    fn write_snapshot(&self, path: PathBuf) {
+        let processes = self
+            .imp()
+            .processes
+            .process_items()
+            .iter()
+            .map(|(process_item, user)| snapshot::process_json(process_item, user))
+            .collect();
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(@weak self as this => async move {
+            let result = tokio::task::spawn(async move {
+                let snapshot = snapshot::snapshot(processes).await;
+                tokio::fs::write(&path, serde_json::to_string_pretty(&snapshot)?)
+                    .await
+                    .with_context(|| format!("unable to write snapshot to {}", path.display()))
+            })
+            .await;
+
+            if let Err(error) = result.map_err(anyhow::Error::from).and_then(|result| result) {
+                log::error!("Unable to save snapshot: {error}");
+
+                let dialog = adw::MessageDialog::builder()
+                    .transient_for(&this)
+                    .modal(true)
+                    .heading(i18n("Unable to Save Snapshot"))
+                    .body(error.to_string())
+                    .build();
+                dialog.add_response("close", &i18n("Close"));
+                dialog.present();
+            }
+        }));
+    }
+
+    /// Asks the user for a location and exports the currently visible
+    /// applications there, as JSON if the file name ends with ".json" and
+    /// as CSV otherwise
+    pub fn export_applications(&self) {
+        let csv_filter = gtk::FileFilter::new();
+        csv_filter.set_name(Some(&i18n("CSV")));
+        csv_filter.add_suffix("csv");
+
+        let json_filter = gtk::FileFilter::new();
+        json_filter.set_name(Some(&i18n("JSON")));
+        json_filter.add_suffix("json");
+
+        let file_chooser = gtk::FileChooserNative::builder()
+            .title(i18n("Export Applications"))
+            .transient_for(self)
+            .modal(true)
+            .action(gtk::FileChooserAction::Save)
+            .accept_label(i18n("Export"))
+            .build();
+        file_chooser.add_filter(&csv_filter);
+        file_chooser.add_filter(&json_filter);
+        file_chooser.set_current_name("resources-applications.csv");
+
+        file_chooser.connect_response(clone!(@weak self as this => move |file_chooser, response| {
+            if response == gtk::ResponseType::Accept {
+                if let Some(path) = file_chooser.file().and_then(|file| file.path()) {
+                    this.write_applications_export(path);
+                }
+            }
+            file_chooser.destroy();
+        }));
+
+        file_chooser.show();
+    }
+
+    fn write_applications_export(&self, path: PathBuf) {
+        let items = self.imp().applications.visible_app_items();
+        let contents = export::export_app_items(&items, ExportFormat::from_path(&path));
+
+        let main_context = MainContext::default();
+        main_context.spawn_local(clone!(@weak self as this => async move {
+            let result = tokio::task::spawn(async move {
+                tokio::fs::write(&path, contents)
+                    .await
+                    .with_context(|| format!("unable to export applications to {}", path.display()))
+            })
+            .await;
+
+            if let Err(error) = result.map_err(anyhow::Error::from).and_then(|result| result) {
+                log::error!("Unable to export applications: {error}");
+
+                let dialog = adw::MessageDialog::builder()
+                    .transient_for(&this)
+                    .modal(true)
+                    .heading(i18n("Unable to Export Applications"))
+                    .body(error.to_string())
+                    .build();
+                dialog.add_response("close", &i18n("Close"));
+                dialog.present();
+            }
+        }));
+    }
+
     pub fn toggle_search(&self) {
         let imp = self.imp();
 
@@ -223,6 +410,69 @@ impl MainWindow {
         }
     }
 
+    /// Applies `action` to the selected app if the Applications page is
+    /// currently visible
+    fn execute_process_action_dialog_selected_apps(&self, action: ProcessAction) {
+        let imp = self.imp();
+
+        let selected_page = imp
+            .content_stack
+            .visible_child()
+            .and_downcast::<adw::ToolbarView>()
+            .and_then(|toolbar| toolbar.content());
+
+        if selected_page.is_some_and(|page| page.is::<ResApplications>()) {
+            imp.applications
+                .execute_process_action_dialog_selected_apps(action);
+        }
+    }
+
+    /// Sets up pausing the refreshes and refreshing manually
+    fn setup_refresh_actions(&self) {
+        let pause_action =
+            gio::SimpleAction::new_stateful("pause-refresh", None, &false.to_variant());
+        pause_action.connect_change_state(clone!(@weak self as this => move |action, state| {
+            let Some(paused) = state.and_then(glib::Variant::get::<bool>) else {
+                return;
+            };
+            action.set_state(&paused.to_variant());
+            let imp = this.imp();
+            imp.refresh_paused.set(paused);
+            imp.paused_banner.set_revealed(paused);
+        }));
+        self.add_action(&pause_action);
+
+        let refresh_now_action = gio::SimpleAction::new("refresh-now", None);
+        refresh_now_action.connect_activate(clone!(@weak self as this => move |_, _| {
+            this.imp().refresh_now.notify_one();
+        }));
+        self.add_action(&refresh_now_action);
+    }
+
+    /// Sets up Delete to end and Shift+Delete to kill the selected app
+    fn setup_shortcuts(&self) {
+        for (name, trigger, action) in [
+            ("end-selected-app", "Delete", ProcessAction::TERM),
+            ("kill-selected-app", "<Shift>Delete", ProcessAction::KILL),
+        ] {
+            let simple_action = gio::SimpleAction::new(name, None);
+            simple_action.connect_activate(clone!(@weak self as this => move |_, _| {
+                this.execute_process_action_dialog_selected_apps(action);
+            }));
+            self.add_action(&simple_action);
+
+            // unlike application-wide accelerators, this controller runs in
+            // the bubble phase, so a focused search entry handles Delete first
+            let controller = gtk::ShortcutController::new();
+            controller.set_propagation_phase(gtk::PropagationPhase::Bubble);
+            controller.add_shortcut(gtk::Shortcut::new(
+                gtk::ShortcutTrigger::parse_string(trigger),
+                Some(gtk::NamedAction::new(&format!("win.{name}"))),
+            ));
+            self.add_controller(controller);
+        }
+    }
+
     async fn init_gpu_pages(self: &MainWindow) -> Vec<GPU> {
         let gpus = GPU::get_gpus().await.unwrap_or_default();
 
@@ -258,6 +508,9 @@ impl MainWindow {
         imp.processes.init(imp.sender.clone());
         imp.memory.init();
 
+        self.setup_shortcuts();
+        self.setup_refresh_actions();
+
         if SETTINGS.show_search_on_start() {
             imp.processes.toggle_search();
             imp.applications.toggle_search();
@@ -280,7 +533,11 @@ impl MainWindow {
         }));
     }
 
-    async fn gather_refresh_data(logical_cpus: usize, gpus: Vec<GPU>) -> RefreshData {
+    async fn gather_refresh_data(
+        logical_cpus: usize,
+        gpus: Vec<GPU>,
+        request: RefreshRequest,
+    ) -> RefreshData {
         let cpu_data = tokio::task::spawn(async move { CpuData::new(logical_cpus).await });
 
         let mem_data = tokio::task::spawn(async move { MemoryData::new().await });
@@ -288,7 +545,9 @@ impl MainWindow {
         let gpu_data = tokio::task::spawn(async move {
             let mut gpu_data_vec = vec![];
             for path in &gpus {
-                let gpu_data = GpuData::new(path).await;
+                let gpu_data = tokio::time::timeout(READ_TIMEOUT, GpuData::new(path))
+                    .await
+                    .ok();
 
                 gpu_data_vec.push(gpu_data);
             }
@@ -297,14 +556,20 @@ impl MainWindow {
         });
 
         let drive_data = tokio::task::spawn(async move {
-            let drive_paths = Drive::get_sysfs_paths().await.unwrap();
+            let mut drive_paths = Drive::get_sysfs_paths().await.unwrap();
 
             let mut drive_data_vec = vec![];
+            let mut gone_paths = vec![];
             for path in &drive_paths {
-                let drive_data = DriveData::new(path).await;
-
-                drive_data_vec.push(drive_data);
+                match tokio::time::timeout(READ_TIMEOUT, DriveData::new(path)).await {
+                    Ok(Ok(drive_data)) => drive_data_vec.push(drive_data),
+                    // the drive has been removed since it was listed, treat it
+                    // like it wasn't there so that its page is removed
+                    Ok(Err(DriveError::NotFound)) => gone_paths.push(path.clone()),
+                    _ => (),
+                }
             }
+            drive_paths.retain(|path| !gone_paths.contains(path));
 
             (drive_paths, drive_data_vec)
         });
@@ -314,15 +579,35 @@ impl MainWindow {
 
             let mut network_data_vec = vec![];
             for path in &network_paths {
-                let network_data = NetworkData::new(path).await;
-
-                network_data_vec.push(network_data);
+                if let Ok(network_data) =
+                    tokio::time::timeout(READ_TIMEOUT, NetworkData::new(path)).await
+                {
+                    network_data_vec.push(network_data);
+                }
             }
 
             (network_paths, network_data_vec)
         });
 
-        let process_data = tokio::task::spawn(async move { Process::all_data().await.unwrap() });
+        let RefreshRequest {
+            remote_address_pids,
+            reverse_lookup,
+            count_open_fds,
+        } = request;
+
+        let process_data =
+            tokio::task::spawn(async move { Process::all_data(count_open_fds).await });
+
+        let remote_addresses = tokio::task::spawn_blocking(move || {
+            remote_address_pids.map(|pids| Process::remote_addresses(&pids))
+        });
+
+        let reverse_lookup_pids = tokio::task::spawn_blocking(move || {
+            reverse_lookup.map(|reverse_lookup| {
+                let pids = reverse_lookup.pids();
+                (reverse_lookup, pids)
+            })
+        });
 
         let cpu_data = cpu_data.await.unwrap();
         let mem_data = mem_data.await.unwrap();
@@ -330,6 +615,8 @@ impl MainWindow {
         let (drive_paths, drive_data) = drive_data.await.unwrap();
         let (network_paths, network_data) = network_data.await.unwrap();
         let process_data = process_data.await.unwrap();
+        let remote_addresses = remote_addresses.await.unwrap();
+        let reverse_lookup_pids = reverse_lookup_pids.await.unwrap();
 
         RefreshData {
             cpu_data,
@@ -340,6 +627,8 @@ impl MainWindow {
             network_paths,
             network_data,
             process_data,
+            remote_addresses,
+            reverse_lookup_pids,
         }
     }
 
@@ -355,11 +644,20 @@ impl MainWindow {
             network_paths,
             network_data,
             process_data,
+            remote_addresses,
+            reverse_lookup_pids,
         } = refresh_data;
 
+        // statistics that couldn't be read at all, these are retried on every refresh
+        let mut stats_errors = Vec::new();
+
         /*
          * Cpu
          */
+        if let Err(error) = &cpu_data.new_total_usage {
+            log::error!("Unable to read processor statistics: {}", error);
+            stats_errors.push(i18n("Processor statistics are unavailable"));
+        }
         imp.cpu.refresh_page(&cpu_data);
 
         /*
@@ -372,6 +670,11 @@ impl MainWindow {
          */
         let gpu_pages = imp.gpu_pages.borrow();
         for (page, gpu_data) in gpu_pages.iter().zip(gpu_data) {
+            // skipped this time because reading took too long
+            let Some(gpu_data) = gpu_data else {
+                continue;
+            };
+
             let page = page.content().and_downcast::<ResGPU>().unwrap();
 
             page.refresh_page(gpu_data);
@@ -382,6 +685,7 @@ impl MainWindow {
          */
         // Make sure there is a page for every drive that is shown
         self.refresh_drive_pages(drive_paths, &drive_data);
+        self.refresh_disk_activity(&drive_data);
 
         // Update drive pages
         for drive_data in drive_data.into_iter() {
@@ -419,11 +723,48 @@ impl MainWindow {
          * Apps and processes
          */
 
-        let mut apps_context = imp.apps_context.borrow_mut();
-        apps_context.refresh(process_data);
+        match process_data {
+            Ok(process_data) => {
+                let mut apps_context = imp.apps_context.borrow_mut();
+                apps_context.refresh(process_data);
 
-        imp.applications.refresh_apps_list(&apps_context);
-        imp.processes.refresh_processes_list(&apps_context);
+                imp.applications.refresh_apps_list(&apps_context);
+                imp.processes.refresh_processes_list(&apps_context);
+            }
+            Err(error) => {
+                log::error!("Unable to read process statistics: {}", error);
+                stats_errors.push(i18n("Process statistics are unavailable"));
+            }
+        }
+
+        if let Some(remote_addresses) = remote_addresses {
+            imp.applications
+                .refresh_dialog_remote_addresses(remote_addresses);
+        }
+
+        // processes might have started or stopped listening or using the file
+        if let Some((reverse_lookup, pids)) = reverse_lookup_pids {
+            imp.processes
+                .refresh_reverse_lookup_pids(&reverse_lookup, pids);
+        }
+
+        if stats_errors.is_empty() {
+            self.hide_stats_error_banner();
+        } else {
+            self.show_stats_error_banner(&stats_errors.join(" · "));
+        }
+    }
+
+    /// Shows a banner above all pages telling the user that some statistics
+    /// couldn't be read, stays until `hide_stats_error_banner()` is called
+    pub fn show_stats_error_banner(&self, msg: &str) {
+        let banner = &self.imp().stats_error_banner;
+        banner.set_title(msg);
+        banner.set_revealed(true);
+    }
+
+    pub fn hide_stats_error_banner(&self) {
+        self.imp().stats_error_banner.set_revealed(false);
     }
 
     async fn periodic_refresh_all(&self, gpus: Vec<GPU>) {
@@ -434,20 +775,32 @@ impl MainWindow {
         let (tx_wait, rx_wait) = std::sync::mpsc::sync_channel(1);
 
         tokio::task::spawn(async move {
+            let mut request = RefreshRequest::default();
             loop {
-                let data = Self::gather_refresh_data(logical_cpus, gpus.clone()).await;
+                let data = Self::gather_refresh_data(logical_cpus, gpus.clone(), request).await;
                 tx_data.send(data).unwrap();
 
                 // Wait on delay so we don't gather data multiple times in a short time span
                 // Which usually just yields the same data and makes changes appear delayed by (up to) multiple refreshes
-                let _wait = rx_wait.recv().unwrap();
+                request = rx_wait.recv().unwrap();
             }
         });
 
+        // a manual refresh also updates the values while paused
+        let mut refresh_requested = false;
+
         loop {
             // gather_refresh_data()
             let refresh_data = rx_data.recv().unwrap();
-            self.refresh_ui(refresh_data);
+            if !imp.refresh_paused.get() || refresh_requested {
+                self.refresh_ui(refresh_data);
+            }
+
+            if let Some(pid) = imp.pid_to_open.take() {
+                imp.content_stack
+                    .set_visible_child(&imp.processes_page.child());
+                imp.processes.open_process_dialog(pid);
+            }
 
             // Total time before next ui refresh
             let total_delay = SETTINGS.refresh_speed().ui_refresh_interval();
@@ -455,15 +808,74 @@ impl MainWindow {
             // Reasonable timespan before total_delay ends to gather all data
             let gather_time = 0.2;
 
-            timeout_future(Duration::from_secs_f32(total_delay - gather_time)).await;
+            // Waiting is cut short if the user wants to refresh right away
+            refresh_requested = glib::future_with_timeout(
+                Duration::from_secs_f32(total_delay - gather_time),
+                Box::pin(imp.refresh_now.notified()),
+            )
+            .await
+            .is_ok();
 
             // Tell other threads to start gethering data
-            tx_wait.send(()).unwrap();
+            // the open file descriptors are shown in their column and in the
+            // app dialog
+            let remote_address_pids = imp.applications.open_dialog_pids();
+            let count_open_fds = SETTINGS.apps_show_open_fds() || remote_address_pids.is_some();
+            tx_wait
+                .send(RefreshRequest {
+                    remote_address_pids,
+                    reverse_lookup: imp.processes.reverse_lookup(),
+                    count_open_fds,
+                })
+                .unwrap();
 
             timeout_future(Duration::from_secs_f32(gather_time)).await;
         }
     }
 
+    /// Shows the summed up throughput of all physical drives since the last
+    /// refresh. Virtual drives are left out because their I/O usually ends up
+    /// on a physical drive and would be counted twice.
+    fn refresh_disk_activity(&self, drive_data: &[DriveData]) {
+        let imp = self.imp();
+
+        let now = Instant::now();
+        let (last_stats, last_timestamp) = imp.last_disk_stats.replace((
+            drive_data
+                .iter()
+                .filter(|data| !data.is_virtual)
+                .map(|data| (data.inner.sysfs_path.clone(), data.disk_stats.clone()))
+                .collect(),
+            Some(now),
+        ));
+        let Some(last_timestamp) = last_timestamp else {
+            return;
+        };
+        let elapsed = now.duration_since(last_timestamp);
+
+        let total = imp
+            .last_disk_stats
+            .borrow()
+            .0
+            .iter()
+            .filter_map(|(path, stats)| {
+                DriveActivity::from_stats(last_stats.get(path)?, stats, elapsed, STAT_SECTOR_SIZE)
+            })
+            .fold(DriveActivity::default(), |total, activity| DriveActivity {
+                read_bytes_per_sec: total.read_bytes_per_sec + activity.read_bytes_per_sec,
+                write_bytes_per_sec: total.write_bytes_per_sec + activity.write_bytes_per_sec,
+            });
+
+        let read = convert_speed(total.read_bytes_per_sec, false);
+        let write = convert_speed(total.write_bytes_per_sec, false);
+        imp.disk_activity
+            .set_label(&i18n_f("↑ {} ↓ {}", &[&write, &read]));
+        imp.disk_activity.set_tooltip_text(Some(&i18n_f(
+            "Total drive activity\nRead: {}\nWrite: {}",
+            &[&read, &write],
+        )));
+    }
+
     /// Create page for every drive that is shown
     fn refresh_drive_pages(&self, mut paths: Vec<PathBuf>, drive_data: &[DriveData]) {
         let imp = self.imp();
@@ -501,10 +913,10 @@ impl MainWindow {
             if !drive_pages.contains_key(&path) {
                 // A drive has been added
 
-                let drive = drive_data
-                    .iter()
-                    .find(|d| d.inner.sysfs_path == path)
-                    .unwrap();
+                // the drive's data may have been skipped because reading took too long, try again next time
+                let Some(drive) = drive_data.iter().find(|d| d.inner.sysfs_path == path) else {
+                    continue;
+                };
 
                 let display_name = drive.inner.display_name(drive.capacity as f64);
 
@@ -559,10 +971,12 @@ impl MainWindow {
             if !network_pages.contains_key(&path) {
                 // A network interface has been added
 
-                let network_interface = network_data
-                    .iter()
-                    .find(|d| d.inner.sysfs_path == path)
-                    .unwrap();
+                // the interface's data may have been skipped because reading took too long, try again next time
+                let Some(network_interface) =
+                    network_data.iter().find(|d| d.inner.sysfs_path == path)
+                else {
+                    continue;
+                };
 
                 // Insert stub page, values will be updated in refresh_page()
                 let page = ResNetwork::new();
@@ -587,14 +1001,27 @@ impl MainWindow {
             match action {
                 Action::ManipulateProcess(action, pid, display_name, toast_overlay) => {
                     if let Some(process) = apps_context.get_process(pid) {
-                        let toast_message = match process.execute_process_action(action) {
-                            Ok(()) => get_action_success(action, &[&display_name]),
+                        let toast = match process.execute_process_action(action) {
+                            Ok(()) => Toast::new(&get_action_success(action, &[&display_name])),
                             Err(e) => {
                                 log::error!("Unable to kill process {}: {}", pid, e);
-                                get_process_action_failure(action, &[&display_name])
+                                let toast = Toast::new(&get_process_action_failure(
+                                    action,
+                                    &[&display_name],
+                                ));
+                                if is_permission_error(&e) {
+                                    this.offer_elevated_retry(
+                                        &toast,
+                                        action,
+                                        vec![pid],
+                                        &display_name,
+                                        &toast_overlay,
+                                    );
+                                }
+                                toast
                             }
                         };
-                        toast_overlay.add_toast(Toast::new(&toast_message));
+                        toast_overlay.add_toast(toast);
                     }
                 }
 
@@ -602,20 +1029,211 @@ impl MainWindow {
                     let app = apps_context.get_app(&id).unwrap();
                     let res = app.execute_process_action(&apps_context, action);
 
-                    for r in &res {
+                    let mut errors: Vec<&anyhow::Error> = Vec::new();
+                    let mut denied_pids = Vec::new();
+                    for (pid, r) in &res {
                         if let Err(e) = r {
-                            log::error!("Unable to kill a process: {}", e);
+                            log::error!("Unable to kill process {}: {}", pid, e);
+                            errors.push(e);
+                            if is_permission_error(e) {
+                                denied_pids.push(*pid);
+                            }
                         }
                     }
 
-                    let processes_tried = res.len();
-                    let processes_successful = res.iter().flatten().count();
-                    let processes_unsuccessful = processes_tried - processes_successful;
+                    let toast_message = if errors.is_empty() {
+                        get_action_success(action, &[&app.display_name])
+                    } else {
+                        get_app_action_failure(
+                            action,
+                            &app.display_name,
+                            res.len() as u32,
+                            &errors,
+                        )
+                    };
+
+                    let toast = Toast::new(&toast_message);
+                    if !denied_pids.is_empty() {
+                        this.offer_elevated_retry(
+                            &toast,
+                            action,
+                            denied_pids,
+                            &app.display_name,
+                            &toast_overlay,
+                        );
+                    }
+                    toast_overlay.add_toast(toast);
+                }
 
-                    let toast_message = if processes_unsuccessful > 0 {
-                        get_app_action_failure(action, processes_unsuccessful as u32)
+                Action::ManipulateProcessesElevated(action, pids, display_name, toast_overlay) => {
+                    let pids: Vec<i32> = pids
+                        .into_iter()
+                        .filter(|pid| apps_context.get_process(*pid).is_some())
+                        .collect();
+                    let processes_amount = pids.len() as u32;
+
+                    // the apps context must not be borrowed while the user
+                    // authenticates, otherwise the refreshes in the meantime
+                    // would panic
+                    drop(apps_context);
+                    let result = if pids.is_empty() {
+                        Ok(())
                     } else {
-                        get_action_success(action, &[&app.display_name])
+                        tokio::task::spawn_blocking(move || {
+                            Process::execute_process_action_elevated_pids(&pids, action)
+                        })
+                        .await
+                        .context("elevated action panicked")
+                        .and_then(|result| result)
+                    };
+
+                    let toast_message = match result {
+                        Ok(()) => get_action_success(action, &[&display_name]),
+                        Err(e) => {
+                            log::error!(
+                                "Unable to kill the processes of {} as administrator: {}",
+                                display_name,
+                                e
+                            );
+                            // the single call only reports one error for all processes
+                            let errors = vec![&e; processes_amount as usize];
+                            get_app_action_failure(action, &display_name, processes_amount, &errors)
+                        }
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+
+                Action::ManipulateApps(action, ids, toast_overlay) => {
+                    let mut apps_successful = 0;
+                    let mut apps_failed = 0;
+
+                    for id in ids {
+                        let Some(app) = apps_context.get_app(&id) else {
+                            continue;
+                        };
+
+                        let res = app.execute_process_action(&apps_context, action);
+                        for e in res.iter().filter_map(|(_, r)| r.as_ref().err()) {
+                            log::error!("Unable to kill a process of {}: {}", id, e);
+                        }
+
+                        if res.iter().all(|(_, r)| r.is_ok()) {
+                            apps_successful += 1;
+                        } else {
+                            apps_failed += 1;
+                        }
+                    }
+
+                    toast_overlay.add_toast(Toast::new(&get_apps_action_result(
+                        action,
+                        apps_successful,
+                        apps_failed,
+                    )));
+                }
+
+                Action::ReniceApp(nice, id, toast_overlay) => {
+                    // the app may have stopped while the dialog was open
+                    let Some(app) = apps_context.get_app(&id) else {
+                        toast_overlay.add_toast(Toast::new(&i18n(
+                            "The application is not running anymore",
+                        )));
+                        return;
+                    };
+                    let res = app.set_nice(&apps_context, nice);
+
+                    for e in res.iter().filter_map(|r| r.as_ref().err()) {
+                        log::error!("Unable to renice a process of {}: {}", id, e);
+                    }
+
+                    let toast_message = if res.iter().all(Result::is_ok) {
+                        i18n_f(
+                            "Successfully changed the priority of {}",
+                            &[&app.display_name],
+                        )
+                    } else if res.iter().filter_map(|r| r.as_ref().err()).any(|e| {
+                        e.downcast_ref::<nix::errno::Errno>() == Some(&nix::errno::Errno::EPERM)
+                    }) {
+                        i18n("Raising the priority requires elevated privileges")
+                    } else {
+                        i18n_f(
+                            "There was a problem changing the priority of {}",
+                            &[&app.display_name],
+                        )
+                    };
+
+                    toast_overlay.add_toast(Toast::new(&toast_message));
+                }
+
+                Action::EndAppForcefully(id, toast_overlay) => {
+                    let Some(app) = apps_context.get_app(&id) else {
+                        return;
+                    };
+                    let display_name = app.display_name.clone();
+
+                    // the PIDs may be reused by unrelated processes once the
+                    // targeted ones have ended, their start times tell them apart
+                    let start_times: HashMap<i32, u64> = app
+                        .processes_iter(&apps_context)
+                        .map(|process| (process.data.pid, process.data.start_time))
+                        .collect();
+
+                    for e in app
+                        .execute_process_action(&apps_context, ProcessAction::TERM)
+                        .iter()
+                        .filter_map(|(_, r)| r.as_ref().err())
+                    {
+                        log::error!("Unable to end a process of {}: {}", id, e);
+                    }
+
+                    // the apps context must not be borrowed while waiting,
+                    // otherwise the refreshes in the meantime would panic
+                    drop(apps_context);
+                    let grace_period = SETTINGS.end_grace_period().max(1) as u64;
+                    timeout_future(Duration::from_secs(grace_period)).await;
+
+                    // the processes are gathered again to also catch children
+                    // that have been spawned during the grace period
+                    let apps_context = imp.apps_context.borrow();
+                    let Some(app) = apps_context.get_app(&id) else {
+                        return;
+                    };
+                    let survivors: Vec<&Process> = app
+                        .processes_iter(&apps_context)
+                        .filter(|process| {
+                            let start_time = start_times
+                                .get(&process.data.pid)
+                                .copied()
+                                .unwrap_or(process.data.start_time);
+                            Process::is_still_running(process.data.pid, start_time)
+                        })
+                        .collect();
+
+                    let toast_message = if survivors.is_empty() {
+                        i18n_f("{} has quit", &[&display_name])
+                    } else {
+                        let results: Vec<Result<()>> = survivors
+                            .iter()
+                            .map(|process| process.execute_process_action(ProcessAction::KILL))
+                            .collect();
+                        let errors: Vec<&anyhow::Error> =
+                            results.iter().filter_map(|r| r.as_ref().err()).collect();
+                        for e in &errors {
+                            log::error!("Unable to kill a process of {}: {}", id, e);
+                        }
+                        if !errors.is_empty() {
+                            get_app_action_failure(
+                                ProcessAction::KILL,
+                                &display_name,
+                                results.len() as u32,
+                                &errors,
+                            )
+                        } else {
+                            i18n_f(
+                                "{} didn't quit in time and has been killed",
+                                &[&display_name],
+                            )
+                        }
                     };
 
                     toast_overlay.add_toast(Toast::new(&toast_message));
@@ -626,6 +1244,31 @@ impl MainWindow {
         glib::ControlFlow::Continue
     }
 
+    /// Adds a button to `toast` that retries `action` on `pids` with elevated
+    /// privileges
+    fn offer_elevated_retry(
+        &self,
+        toast: &Toast,
+        action: ProcessAction,
+        pids: Vec<i32>,
+        display_name: &str,
+        toast_overlay: &ToastOverlay,
+    ) {
+        toast.set_button_label(Some(&i18n("Retry as Administrator")));
+        let display_name = display_name.to_string();
+        toast.connect_button_clicked(clone!(@weak self as this, @weak toast_overlay => move |_| {
+            send!(
+                this.imp().sender,
+                Action::ManipulateProcessesElevated(
+                    action,
+                    pids.clone(),
+                    display_name.clone(),
+                    toast_overlay,
+                )
+            );
+        }));
+    }
+
     fn save_window_size(&self) -> Result<(), glib::BoolError> {
         let (width, height) = self.default_size();
 
@@ -696,12 +1339,50 @@ impl Default for MainWindow {
     }
 }
 
+/// Whether the user wants to be asked before `action` is applied to apps or
+/// processes
+pub fn needs_confirmation(action: ProcessAction) -> bool {
+    match action {
+        // Nothing too bad can happen on Continue so never ask
+        ProcessAction::CONT => false,
+        ProcessAction::TERM => SETTINGS.confirm_process_actions(),
+        ProcessAction::STOP | ProcessAction::KILL | ProcessAction::SIGNAL(_) => {
+            SETTINGS.confirm_process_actions() || SETTINGS.always_confirm_destructive_actions()
+        }
+    }
+}
+
 pub fn get_action_name(action: ProcessAction, args: &[&str]) -> String {
     match action {
         ProcessAction::TERM => i18n_f("End {}?", args),
         ProcessAction::STOP => i18n_f("Halt {}?", args),
         ProcessAction::KILL => i18n_f("Kill {}?", args),
         ProcessAction::CONT => i18n_f("Continue {}?", args),
+        ProcessAction::SIGNAL(signal) => {
+            i18n_f("Send {} to {}?", &[&[signal.as_str()][..], args].concat())
+        }
+    }
+}
+
+/// Like `get_action_name()`, but for several applications at once
+pub fn get_apps_action_name(action: ProcessAction, apps: u32) -> String {
+    let args: &[&str] = &[&apps.to_string()];
+    match action {
+        ProcessAction::TERM => ni18n_f("End {} application?", "End {} applications?", apps, args),
+        ProcessAction::STOP => ni18n_f("Halt {} application?", "Halt {} applications?", apps, args),
+        ProcessAction::KILL => ni18n_f("Kill {} application?", "Kill {} applications?", apps, args),
+        ProcessAction::CONT => ni18n_f(
+            "Continue {} application?",
+            "Continue {} applications?",
+            apps,
+            args,
+        ),
+        ProcessAction::SIGNAL(signal) => ni18n_f(
+            "Send {} to {} application?",
+            "Send {} to {} applications?",
+            apps,
+            &[&[signal.as_str()][..], args].concat(),
+        ),
     }
 }
 
@@ -711,6 +1392,7 @@ pub fn get_app_action_warning(action: ProcessAction) -> String {
             ProcessAction::STOP => i18n("Halting an application can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::KILL => i18n("Killing an application can come with serious risks such as losing data and security implications. Use with caution."),
             ProcessAction::CONT => String::new(),
+            ProcessAction::SIGNAL(_) => i18n("Depending on the signal, the application might behave unexpectedly or end. Use with caution."),
         }
 }
 
@@ -720,6 +1402,68 @@ pub fn get_app_action_description(action: ProcessAction) -> String {
         ProcessAction::STOP => i18n("Halt application"),
         ProcessAction::KILL => i18n("Kill application"),
         ProcessAction::CONT => i18n("Continue application"),
+        ProcessAction::SIGNAL(_) => i18n("Send signal"),
+    }
+}
+
+pub fn get_apps_action_description(action: ProcessAction) -> String {
+    match action {
+        ProcessAction::TERM => i18n("End applications"),
+        ProcessAction::STOP => i18n("Halt applications"),
+        ProcessAction::KILL => i18n("Kill applications"),
+        ProcessAction::CONT => i18n("Continue applications"),
+        ProcessAction::SIGNAL(_) => i18n("Send signal"),
+    }
+}
+
+/// Summarizes how applying `action` to several applications went
+pub fn get_apps_action_result(action: ProcessAction, successful: u32, failed: u32) -> String {
+    if failed > 0 {
+        let args: &[&str] = &[&successful.to_string(), &failed.to_string()];
+        return match action {
+            ProcessAction::TERM => i18n_f("Ended {} applications, {} failed", args),
+            ProcessAction::STOP => i18n_f("Halted {} applications, {} failed", args),
+            ProcessAction::KILL => i18n_f("Killed {} applications, {} failed", args),
+            ProcessAction::CONT => i18n_f("Continued {} applications, {} failed", args),
+            ProcessAction::SIGNAL(signal) => i18n_f(
+                "Sent {} to {} applications, {} failed",
+                &[&[signal.as_str()][..], args].concat(),
+            ),
+        };
+    }
+
+    let args: &[&str] = &[&successful.to_string()];
+    match action {
+        ProcessAction::TERM => ni18n_f(
+            "Ended {} application",
+            "Ended {} applications",
+            successful,
+            args,
+        ),
+        ProcessAction::STOP => ni18n_f(
+            "Halted {} application",
+            "Halted {} applications",
+            successful,
+            args,
+        ),
+        ProcessAction::KILL => ni18n_f(
+            "Killed {} application",
+            "Killed {} applications",
+            successful,
+            args,
+        ),
+        ProcessAction::CONT => ni18n_f(
+            "Continued {} application",
+            "Continued {} applications",
+            successful,
+            args,
+        ),
+        ProcessAction::SIGNAL(signal) => ni18n_f(
+            "Sent {} to {} application",
+            "Sent {} to {} applications",
+            successful,
+            &[&[signal.as_str()][..], args].concat(),
+        ),
     }
 }
 
@@ -729,34 +1473,118 @@ pub fn get_action_success(action: ProcessAction, args: &[&str]) -> String {
         ProcessAction::STOP => i18n_f("Successfully halted {}", args),
         ProcessAction::KILL => i18n_f("Successfully killed {}", args),
         ProcessAction::CONT => i18n_f("Successfully continued {}", args),
+        ProcessAction::SIGNAL(signal) => i18n_f(
+            "Successfully sent {} to {}",
+            &[&[signal.as_str()][..], args].concat(),
+        ),
+    }
+}
+
+/// Describes why sending a signal failed, using the `Errno` of the failed
+/// `kill` if there is one
+fn action_failure_reason(error: &anyhow::Error) -> String {
+    match error.downcast_ref::<Errno>() {
+        Some(Errno::EPERM) => i18n("permission denied"),
+        Some(Errno::ESRCH) => i18n("no such process"),
+        Some(Errno::EINVAL) => i18n("invalid signal"),
+        Some(Errno::UnknownErrno) | None => i18n("unknown error"),
+        Some(errno) => errno.desc().to_lowercase(),
     }
 }
 
-pub fn get_app_action_failure(action: ProcessAction, args: u32) -> String {
+/// Returns a message like "Ended Firefox (3 of 5 processes; 2 failed:
+/// permission denied)" or, if no process could be signalled at all, "Unable
+/// to end Firefox (5 processes failed: permission denied)"
+pub fn get_app_action_failure(
+    action: ProcessAction,
+    name: &str,
+    processes_tried: u32,
+    errors: &[&anyhow::Error],
+) -> String {
+    let mut reasons: Vec<String> = Vec::new();
+    for reason in errors.iter().map(|e| action_failure_reason(e)) {
+        if !reasons.contains(&reason) {
+            reasons.push(reason);
+        }
+    }
+    let reasons = reasons.join(", ");
+
+    let processes_failed = errors.len() as u32;
+    let processes_successful = processes_tried.saturating_sub(processes_failed);
+
+    if processes_successful == 0 {
+        let args: &[&str] = &[name, &processes_failed.to_string(), &reasons];
+        return match action {
+            ProcessAction::TERM => ni18n_f(
+                "Unable to end {} ({} process failed: {})",
+                "Unable to end {} ({} processes failed: {})",
+                processes_failed,
+                args,
+            ),
+            ProcessAction::STOP => ni18n_f(
+                "Unable to halt {} ({} process failed: {})",
+                "Unable to halt {} ({} processes failed: {})",
+                processes_failed,
+                args,
+            ),
+            ProcessAction::KILL => ni18n_f(
+                "Unable to kill {} ({} process failed: {})",
+                "Unable to kill {} ({} processes failed: {})",
+                processes_failed,
+                args,
+            ),
+            ProcessAction::CONT => ni18n_f(
+                "Unable to continue {} ({} process failed: {})",
+                "Unable to continue {} ({} processes failed: {})",
+                processes_failed,
+                args,
+            ),
+            ProcessAction::SIGNAL(signal) => ni18n_f(
+                "Unable to send {} to {} ({} process failed: {})",
+                "Unable to send {} to {} ({} processes failed: {})",
+                processes_failed,
+                &[&[signal.as_str()][..], args].concat(),
+            ),
+        };
+    }
+
+    let args: &[&str] = &[
+        name,
+        &processes_successful.to_string(),
+        &processes_tried.to_string(),
+        &processes_failed.to_string(),
+        &reasons,
+    ];
     match action {
         ProcessAction::TERM => ni18n_f(
-            "There was a problem ending a process",
-            "There were problems ending {} processes",
+            "Ended {} ({} of {} process; {} failed: {})",
+            "Ended {} ({} of {} processes; {} failed: {})",
+            processes_tried,
             args,
-            &[&args.to_string()],
         ),
         ProcessAction::STOP => ni18n_f(
-            "There was a problem halting a process",
-            "There were problems halting {} processes",
+            "Halted {} ({} of {} process; {} failed: {})",
+            "Halted {} ({} of {} processes; {} failed: {})",
+            processes_tried,
             args,
-            &[&args.to_string()],
         ),
         ProcessAction::KILL => ni18n_f(
-            "There was a problem killing a process",
-            "There were problems killing {} processes",
+            "Killed {} ({} of {} process; {} failed: {})",
+            "Killed {} ({} of {} processes; {} failed: {})",
+            processes_tried,
             args,
-            &[&args.to_string()],
         ),
         ProcessAction::CONT => ni18n_f(
-            "There was a problem continuing a process",
-            "There were problems continuing {} processes",
+            "Continued {} ({} of {} process; {} failed: {})",
+            "Continued {} ({} of {} processes; {} failed: {})",
+            processes_tried,
             args,
-            &[&args.to_string()],
+        ),
+        ProcessAction::SIGNAL(signal) => ni18n_f(
+            "Sent {} to {} ({} of {} process; {} failed: {})",
+            "Sent {} to {} ({} of {} processes; {} failed: {})",
+            processes_tried,
+            &[&[signal.as_str()][..], args].concat(),
         ),
     }
 }
@@ -767,5 +1595,9 @@ pub fn get_process_action_failure(action: ProcessAction, args: &[&str]) -> Strin
         ProcessAction::STOP => i18n_f("There was a problem halting {}", args),
         ProcessAction::KILL => i18n_f("There was a problem killing {}", args),
         ProcessAction::CONT => i18n_f("There was a problem continuing {}", args),
+        ProcessAction::SIGNAL(signal) => i18n_f(
+            "There was a problem sending {} to {}",
+            &[&[signal.as_str()][..], args].concat(),
+        ),
     }
 }
@@ -1,14 +1,16 @@
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 use anyhow::{Context, Result};
 use gtk::gio::{Icon, ThemedIcon};
 use hashbrown::{HashMap, HashSet};
 use once_cell::sync::Lazy;
-use process_data::{Containerization, ProcessData};
+use process_data::{AllProcessData, Containerization};
 
 use crate::i18n::i18n;
 
-use super::process::{Process, ProcessAction, ProcessItem};
+use super::process::{self, Process, ProcessAction, ProcessItem, ProcessState, TrustLevel};
+use super::settings::{GroupingMode, SETTINGS};
 
 // Adapted from Mission Center: https://gitlab.com/mission-center-devs/mission-center/
 static DATA_DIRS: Lazy<Vec<PathBuf>> = Lazy::new(|| {
@@ -39,10 +41,17 @@ pub struct AppsContext {
     apps: HashMap<String, App>,
     processes: HashMap<i32, Process>,
     processes_assigned_to_apps: HashSet<i32>,
+    /// All processes grouped by the binary they're running, keyed by IDs
+    /// starting with `EXECUTABLE_ID_PREFIX`
+    executable_groups: HashMap<String, App>,
     read_bytes_from_dead_processes: u64,
     write_bytes_from_dead_processes: u64,
 }
 
+/// Prefix of the IDs of processes grouped by their executable, keeps them
+/// apart from the IDs of installed apps
+const EXECUTABLE_ID_PREFIX: &str = "exe:";
+
 /// Convenience struct for displaying running applications and
 /// displaying a "System Processes" item.
 #[derive(Debug, Clone)]
@@ -52,13 +61,78 @@ pub struct AppItem {
     pub icon: Icon,
     pub description: Option<String>,
     pub memory_usage: usize,
+    /// Resident memory that may be shared with other processes, not part of
+    /// `memory_usage`
+    pub shared_memory_usage: usize,
+    pub swap_usage: usize,
     pub cpu_time_ratio: f32,
     pub processes_amount: usize,
+    pub pids: Vec<i32>,
     pub containerization: Containerization,
     pub read_speed: f64,
     pub read_total: u64,
     pub write_speed: f64,
     pub write_total: u64,
+    /// Estimated network throughput in bytes per second, `None` if none of
+    /// the processes has its own network namespace, see `Process::net_speed()`
+    pub net_speed: Option<f64>,
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    /// For how long the earliest process of this item has been running
+    pub uptime: Duration,
+    /// The lowest (i.e. most favorable) nice value of this item's processes
+    pub nice: i32,
+    /// The sorted IDs of the users owning this item's processes
+    pub uids: Vec<u32>,
+    /// The most alarming state of this item's processes
+    pub state: ProcessState,
+    /// Amount of open file descriptors, only counted while the corresponding
+    /// column or the app's dialog is shown and `None` if they couldn't be
+    /// counted for at least one of the processes
+    pub open_fds: Option<usize>,
+    /// Whether any of the processes has root privileges, see
+    /// `Process::is_elevated()`
+    pub elevated: bool,
+}
+
+/// The changes that need to be applied to a list of displayed `AppItem`s to
+/// reflect the current state of the running apps, see
+/// `AppsContext::app_items_diff()`
+#[derive(Debug, Clone, Default)]
+pub struct AppItemsDiff {
+    /// Items that aren't displayed yet
+    pub added: Vec<AppItem>,
+    /// IDs of displayed items whose apps have stopped running
+    pub removed: HashSet<Option<String>>,
+    /// New values for items that are already displayed
+    pub updated: HashMap<Option<String>, AppItem>,
+}
+
+impl AppItem {
+    /// Whether all processes of this item have already ended and are only
+    /// waiting to be reaped by their parents, so signals can't do anything
+    #[must_use]
+    pub fn is_zombie(&self) -> bool {
+        self.processes_amount == 1 && self.state == ProcessState::Zombie
+    }
+
+    /// Whether this item stands for a set of processes that actions are
+    /// executed on as a whole, i.e. an app or a group of processes running the
+    /// same executable, but not System Processes
+    #[must_use]
+    pub fn is_app(&self) -> bool {
+        self.id.is_some()
+    }
+
+    pub fn trust_level(&self) -> TrustLevel {
+        if self.containerization == Containerization::Flatpak {
+            TrustLevel::Sandboxed
+        } else if self.elevated {
+            TrustLevel::Elevated
+        } else {
+            TrustLevel::Normal
+        }
+    }
 }
 
 /// Represents an application installed on the system. It doesn't
@@ -146,9 +220,12 @@ impl App {
         !self.processes.is_empty()
     }
 
+    /// Iterates over the processes of this app by looking up its PIDs, so
+    /// this only takes as long as the app has processes
     pub fn processes_iter<'a>(&'a self, apps: &'a AppsContext) -> impl Iterator<Item = &Process> {
-        apps.all_processes()
-            .filter(move |process| self.processes.contains(&process.data.pid))
+        self.processes
+            .iter()
+            .filter_map(|pid| apps.get_process(*pid))
     }
 
     pub fn processes_iter_mut<'a>(
@@ -159,61 +236,38 @@ impl App {
             .filter(move |process| self.processes.contains(&process.data.pid))
     }
 
-    #[must_use]
-    pub fn memory_usage(&self, apps: &AppsContext) -> usize {
-        self.processes_iter(apps)
-            .map(|process| process.data.memory_usage)
-            .sum()
-    }
-
-    #[must_use]
-    pub fn cpu_time_ratio(&self, apps: &AppsContext) -> f32 {
-        self.processes_iter(apps)
-            .map(Process::cpu_time_ratio)
-            .sum::<f32>()
-            .clamp(0.0, 1.0)
-    }
-
-    #[must_use]
-    pub fn read_speed(&self, apps: &AppsContext) -> f64 {
+    pub fn set_nice(&self, apps: &AppsContext, nice: i32) -> Vec<Result<()>> {
         self.processes_iter(apps)
-            .filter_map(Process::read_speed)
-            .sum()
-    }
-
-    #[must_use]
-    pub fn read_total(&self, apps: &AppsContext) -> u64 {
-        self.read_bytes_from_dead_processes.saturating_add(
-            self.processes_iter(apps)
-                .filter_map(|process| process.data.read_bytes)
-                .sum::<u64>(),
-        )
-    }
-
-    #[must_use]
-    pub fn write_speed(&self, apps: &AppsContext) -> f64 {
-        self.processes_iter(apps)
-            .filter_map(Process::write_speed)
-            .sum()
-    }
-
-    #[must_use]
-    pub fn write_total(&self, apps: &AppsContext) -> u64 {
-        self.write_bytes_from_dead_processes.saturating_add(
-            self.processes_iter(apps)
-                .filter_map(|process| process.data.write_bytes)
-                .sum::<u64>(),
-        )
+            .map(|process| process.set_nice(nice))
+            .collect()
     }
 
+    /// Executes `action` on every process of this app and returns the result
+    /// for each PID. Failures caused by the signal itself carry the `Errno`
+    /// of the failed `kill` as their source.
     pub fn execute_process_action(
         &self,
         apps: &AppsContext,
         action: ProcessAction,
-    ) -> Vec<Result<()>> {
-        self.processes_iter(apps)
-            .map(|process| process.execute_process_action(action))
-            .collect()
+    ) -> Vec<(i32, Result<()>)> {
+        // the results for the individual PIDs are logged by `Process`
+        log::debug!(
+            "Sending {} to the processes of {}",
+            action.kill_arg(),
+            self.id
+        );
+        let results = self
+            .processes_iter(apps)
+            .map(|process| (process.data.pid, process.execute_process_action(action)))
+            .collect::<Vec<_>>();
+        log::debug!(
+            "Sent {} to {} of {} processes of {}",
+            action.kill_arg(),
+            results.iter().filter(|(_, result)| result.is_ok()).count(),
+            results.len(),
+            self.id
+        );
+        results
     }
 }
 
@@ -231,6 +285,7 @@ impl AppsContext {
             apps,
             processes: HashMap::new(),
             processes_assigned_to_apps: HashSet::new(),
+            executable_groups: HashMap::new(),
             read_bytes_from_dead_processes: 0,
             write_bytes_from_dead_processes: 0,
         }
@@ -286,7 +341,7 @@ impl AppsContext {
     }
 
     pub fn get_app(&self, id: &str) -> Option<&App> {
-        self.apps.get(id)
+        self.apps.get(id).or_else(|| self.executable_groups.get(id))
     }
 
     #[must_use]
@@ -294,6 +349,18 @@ impl AppsContext {
         self.processes.values()
     }
 
+    /// Returns the sorted IDs of all users that currently run processes
+    #[must_use]
+    pub fn active_uids(&self) -> Vec<u32> {
+        let mut uids: Vec<u32> = self
+            .all_processes()
+            .map(|process| process.data.uid)
+            .collect();
+        uids.sort_unstable();
+        uids.dedup();
+        uids
+    }
+
     #[must_use]
     pub fn all_processes_mut(&mut self) -> impl Iterator<Item = &mut Process> {
         self.processes.values_mut()
@@ -317,69 +384,164 @@ impl AppsContext {
             };
             ProcessItem {
                 pid: process.data.pid,
-                display_name: full_comm.clone(),
+                // the interpreter's name is still part of the commandline, so it remains searchable
+                display_name: process.script_name().unwrap_or_else(|| full_comm.clone()),
                 icon: process.icon.clone(),
                 memory_usage: process.data.memory_usage,
                 cpu_time_ratio: process.cpu_time_ratio(),
+                user_cpu_ratio: process.user_cpu_ratio(),
+                system_cpu_ratio: process.system_cpu_ratio(),
                 commandline: Process::sanitize_cmdline(process.data.commandline.clone())
                     .unwrap_or(full_comm),
+                cmdline: Process::cmdline_args(&process.data.commandline),
                 containerization: process.data.containerization.clone(),
                 cgroup: process.data.cgroup.clone(),
+                cgroup_kind: process.data.cgroup_kind,
                 uid: process.data.uid,
                 read_speed: process.read_speed(),
                 read_total: process.data.read_bytes,
                 write_speed: process.write_speed(),
                 write_total: process.data.write_bytes,
+                minor_faults: process.data.minor_faults,
+                major_faults: process.data.major_faults,
+                // only interesting for sleeping (S) or blocked (D) processes
+                wchan: process
+                    .data
+                    .wchan
+                    .clone()
+                    .filter(|_| matches!(process.data.state, 'S' | 'D')),
+                state: ProcessState::from_stat(process.data.state),
+                effective_capabilities: process.data.effective_capabilities,
+                elevated: process.is_elevated(),
+                nice: process.data.nice,
+                swap_usage: process.data.swap_usage,
+                open_fds: process.data.open_fds,
+                start_time: process.start_time(),
+                net_speed: process.net_speed(),
             }
         })
     }
 
+    /// Returns the `AppItem` of `app`, whose processes might also be grouped
+    /// by their executable instead of an installed application
+    fn app_item(&self, app: &App, system_uptime: Duration) -> AppItem {
+        // looked up only once, all of the statistics are computed from these
+        let processes: Vec<&Process> = app.processes_iter(self).collect();
+
+        let containerization = if processes
+            .iter()
+            .filter(|process| {
+                !process.data.commandline.starts_with("bwrap")
+                    && !process.data.commandline.is_empty()
+            })
+            .any(|process| process.data.containerization == Containerization::Flatpak)
+        {
+            Containerization::Flatpak
+        } else {
+            Containerization::None
+        };
+
+        let start_time = processes
+            .iter()
+            .map(|process| process.start_time())
+            .min()
+            .unwrap_or_default();
+
+        let mut pids: Vec<i32> = processes.iter().map(|process| process.data.pid).collect();
+        pids.sort_unstable();
+
+        let mut uids: Vec<u32> = processes.iter().map(|process| process.data.uid).collect();
+        uids.sort_unstable();
+        uids.dedup();
+
+        AppItem {
+            id: Some(app.id.clone()),
+            display_name: app.display_name.clone(),
+            icon: app.icon.clone(),
+            description: app.description.clone(),
+            memory_usage: processes
+                .iter()
+                .map(|process| process.data.memory_usage)
+                .sum(),
+            shared_memory_usage: processes
+                .iter()
+                .map(|process| process.data.shared_memory_usage)
+                .sum(),
+            swap_usage: processes
+                .iter()
+                .map(|process| process.data.swap_usage)
+                .sum(),
+            cpu_time_ratio: processes
+                .iter()
+                .map(|process| process.cpu_time_ratio())
+                .sum::<f32>()
+                .clamp(0.0, 1.0),
+            processes_amount: processes.len(),
+            pids,
+            containerization,
+            read_speed: processes
+                .iter()
+                .filter_map(|process| process.read_speed())
+                .sum(),
+            read_total: app.read_bytes_from_dead_processes.saturating_add(
+                processes
+                    .iter()
+                    .filter_map(|process| process.data.read_bytes)
+                    .sum::<u64>(),
+            ),
+            write_speed: processes
+                .iter()
+                .filter_map(|process| process.write_speed())
+                .sum(),
+            write_total: app.write_bytes_from_dead_processes.saturating_add(
+                processes
+                    .iter()
+                    .filter_map(|process| process.data.write_bytes)
+                    .sum::<u64>(),
+            ),
+            net_speed: net_speed(processes.iter().copied()),
+            minor_faults: processes
+                .iter()
+                .map(|process| process.data.minor_faults)
+                .sum(),
+            major_faults: processes
+                .iter()
+                .map(|process| process.data.major_faults)
+                .sum(),
+            uptime: system_uptime.saturating_sub(start_time),
+            nice: processes
+                .iter()
+                .map(|process| process.data.nice)
+                .min()
+                .unwrap_or_default(),
+            uids,
+            state: process_state(processes.iter().copied()),
+            open_fds: processes.iter().map(|process| process.data.open_fds).sum(),
+            elevated: processes.iter().any(|process| process.is_elevated()),
+        }
+    }
+
     /// Returns a `HashMap` of running graphical applications. For more info,
     /// refer to `AppItem`.
     #[must_use]
     pub fn app_items(&self) -> HashMap<Option<String>, AppItem> {
-        let mut app_pids = HashSet::new();
+        let system_uptime = process::system_uptime().unwrap_or_default();
+
+        // every process belongs to some executable, so there are no System
+        // Processes in this mode
+        if SETTINGS.apps_grouping() == GroupingMode::Executable {
+            return self
+                .executable_groups
+                .values()
+                .map(|app| (Some(app.id.clone()), self.app_item(app, system_uptime)))
+                .collect();
+        }
 
         let mut return_map = self
             .apps
             .iter()
             .filter(|(_, app)| app.is_running() && !app.id.starts_with("xdg-desktop-portal"))
-            .map(|(_, app)| {
-                app.processes_iter(self).for_each(|process| {
-                    app_pids.insert(process.data.pid);
-                });
-
-                let containerization = if app
-                    .processes_iter(self)
-                    .filter(|process| {
-                        !process.data.commandline.starts_with("bwrap")
-                            && !process.data.commandline.is_empty()
-                    })
-                    .any(|process| process.data.containerization == Containerization::Flatpak)
-                {
-                    Containerization::Flatpak
-                } else {
-                    Containerization::None
-                };
-
-                (
-                    Some(app.id.clone()),
-                    AppItem {
-                        id: Some(app.id.clone()),
-                        display_name: app.display_name.clone(),
-                        icon: app.icon.clone(),
-                        description: app.description.clone(),
-                        memory_usage: app.memory_usage(self),
-                        cpu_time_ratio: app.cpu_time_ratio(self),
-                        processes_amount: app.processes_iter(self).count(),
-                        containerization,
-                        read_speed: app.read_speed(self),
-                        read_total: app.read_total(self),
-                        write_speed: app.write_speed(self),
-                        write_total: app.write_total(self),
-                    },
-                )
-            })
+            .map(|(_, app)| (Some(app.id.clone()), self.app_item(app, system_uptime)))
             .collect::<HashMap<Option<String>, AppItem>>();
 
         let system_cpu_ratio = self
@@ -392,6 +554,16 @@ impl AppsContext {
             .map(|process| process.data.memory_usage)
             .sum();
 
+        let system_shared_memory_usage: usize = self
+            .system_processes_iter()
+            .map(|process| process.data.shared_memory_usage)
+            .sum();
+
+        let system_swap_usage: usize = self
+            .system_processes_iter()
+            .map(|process| process.data.swap_usage)
+            .sum();
+
         let system_read_speed = self
             .system_processes_iter()
             .filter_map(|process| process.read_speed())
@@ -414,6 +586,35 @@ impl AppsContext {
                 .filter_map(|process| process.data.write_bytes)
                 .sum::<u64>();
 
+        let system_minor_faults = self
+            .system_processes_iter()
+            .map(|process| process.data.minor_faults)
+            .sum();
+
+        let system_major_faults = self
+            .system_processes_iter()
+            .map(|process| process.data.major_faults)
+            .sum();
+
+        let system_start_time = self
+            .system_processes_iter()
+            .map(Process::start_time)
+            .min()
+            .unwrap_or_default();
+
+        let mut system_pids: Vec<i32> = self
+            .system_processes_iter()
+            .map(|process| process.data.pid)
+            .collect();
+        system_pids.sort_unstable();
+
+        let mut system_uids: Vec<u32> = self
+            .system_processes_iter()
+            .map(|process| process.data.uid)
+            .collect();
+        system_uids.sort_unstable();
+        system_uids.dedup();
+
         return_map.insert(
             None,
             AppItem {
@@ -422,37 +623,91 @@ impl AppsContext {
                 icon: ThemedIcon::new("system-processes").into(),
                 description: None,
                 memory_usage: system_memory_usage,
+                shared_memory_usage: system_shared_memory_usage,
+                swap_usage: system_swap_usage,
                 cpu_time_ratio: system_cpu_ratio,
                 processes_amount: self.processes.len(),
+                pids: system_pids,
                 containerization: Containerization::None,
                 read_speed: system_read_speed,
                 read_total: system_read_total,
                 write_speed: system_write_speed,
                 write_total: system_write_total,
+                net_speed: net_speed(self.system_processes_iter()),
+                minor_faults: system_minor_faults,
+                major_faults: system_major_faults,
+                uptime: system_uptime.saturating_sub(system_start_time),
+                nice: self
+                    .system_processes_iter()
+                    .map(|process| process.data.nice)
+                    .min()
+                    .unwrap_or_default(),
+                uids: system_uids,
+                state: process_state(self.system_processes_iter()),
+                open_fds: self
+                    .system_processes_iter()
+                    .map(|process| process.data.open_fds)
+                    .sum(),
+                elevated: self.system_processes_iter().any(Process::is_elevated),
             },
         );
         return_map
     }
 
+    /// Compares the IDs of the currently `displayed` items with the running
+    /// apps and returns which of them have to be added, removed or updated.
+    /// This includes "System Processes" (`None`), which doesn't exist when
+    /// grouping by executable.
+    #[must_use]
+    pub fn app_items_diff(&self, displayed: &HashSet<Option<String>>) -> AppItemsDiff {
+        let mut diff = AppItemsDiff::default();
+
+        for (id, item) in self.app_items() {
+            if displayed.contains(&id) {
+                diff.updated.insert(id, item);
+            } else {
+                diff.added.push(item);
+            }
+        }
+
+        diff.removed = displayed
+            .iter()
+            .filter(|id| !diff.updated.contains_key(*id))
+            .cloned()
+            .collect();
+
+        diff
+    }
+
     /// Refreshes the statistics about the running applications and processes.
-    pub fn refresh(&mut self, process_data: Vec<ProcessData>) {
+    pub fn refresh(&mut self, all_process_data: AllProcessData) {
+        let AllProcessData {
+            processes: process_data,
+            timed_out,
+        } = all_process_data;
+
         let newly_gathered_processes = process_data
             .into_iter()
             .map(Process::from_process_data)
             .collect::<Vec<_>>();
 
-        let mut updated_processes = HashSet::new();
+        // processes whose data couldn't be read in time are still alive, they just keep their last-known values
+        let mut updated_processes: HashSet<i32> = timed_out.into_iter().collect();
 
         for mut new_process in newly_gathered_processes {
             updated_processes.insert(new_process.data.pid);
             // refresh our old processes
             if let Some(old_process) = self.processes.get_mut(&new_process.data.pid) {
                 old_process.cpu_time_last = old_process.data.cpu_time;
+                old_process.user_cpu_time_last = old_process.data.user_cpu_time;
+                old_process.system_cpu_time_last = old_process.data.system_cpu_time;
                 old_process.cpu_time_last_timestamp = old_process.data.cpu_time_timestamp;
                 old_process.read_bytes_last = old_process.data.read_bytes;
                 old_process.read_bytes_last_timestamp = old_process.data.read_bytes_timestamp;
                 old_process.write_bytes_last = old_process.data.write_bytes;
                 old_process.write_bytes_last_timestamp = old_process.data.write_bytes_timestamp;
+                old_process.net_bytes_last = old_process.data.net_bytes;
+                old_process.net_bytes_last_timestamp = old_process.data.net_bytes_timestamp;
                 old_process.data = new_process.data.clone();
             } else {
                 // this is a new process, see if it belongs to a graphical app
@@ -478,34 +733,38 @@ impl AppsContext {
 
         // all the not-updated processes have unfortunately died, probably
 
-        // collect the I/O stats for died app processes so an app doesn't suddenly have less total disk I/O
-        self.apps.values_mut().for_each(|app| {
-            let (read_dead, write_dead) = app
-                .processes
-                .iter()
-                .filter(|pid| !updated_processes.contains(*pid)) // only dead processes
-                .filter_map(|pid| self.processes.get(pid)) // ignore about non-existing processes
-                .map(|process| (process.data.read_bytes, process.data.write_bytes)) // get their read_bytes and write_bytes
-                .filter_map(
-                    // filter out any processes whose IO stats we were not allowed to see
-                    |(read_bytes, write_bytes)| match (read_bytes, write_bytes) {
-                        (Some(read), Some(write)) => Some((read, write)),
-                        _ => None,
-                    },
-                )
-                .reduce(|sum, current| (sum.0 + current.0, sum.1 + current.1)) // sum them up
-                .unwrap_or((0, 0)); // if there were no processes, it's 0 for both
-
-            app.read_bytes_from_dead_processes += read_dead;
-            app.write_bytes_from_dead_processes += write_dead;
-
-            app.processes.retain(|pid| updated_processes.contains(pid));
-
-            if !app.is_running() {
-                app.read_bytes_from_dead_processes = 0;
-                app.write_bytes_from_dead_processes = 0;
-            }
-        });
+        // collect the I/O stats for died app processes so an app doesn't suddenly have less total disk I/O,
+        // the same goes for the processes grouped by their executable
+        self.apps
+            .values_mut()
+            .chain(self.executable_groups.values_mut())
+            .for_each(|app| {
+                let (read_dead, write_dead) = app
+                    .processes
+                    .iter()
+                    .filter(|pid| !updated_processes.contains(*pid)) // only dead processes
+                    .filter_map(|pid| self.processes.get(pid)) // ignore about non-existing processes
+                    .map(|process| (process.data.read_bytes, process.data.write_bytes)) // get their read_bytes and write_bytes
+                    .filter_map(
+                        // filter out any processes whose IO stats we were not allowed to see
+                        |(read_bytes, write_bytes)| match (read_bytes, write_bytes) {
+                            (Some(read), Some(write)) => Some((read, write)),
+                            _ => None,
+                        },
+                    )
+                    .reduce(|sum, current| (sum.0 + current.0, sum.1 + current.1)) // sum them up
+                    .unwrap_or((0, 0)); // if there were no processes, it's 0 for both
+
+                app.read_bytes_from_dead_processes += read_dead;
+                app.write_bytes_from_dead_processes += write_dead;
+
+                app.processes.retain(|pid| updated_processes.contains(pid));
+
+                if !app.is_running() {
+                    app.read_bytes_from_dead_processes = 0;
+                    app.write_bytes_from_dead_processes = 0;
+                }
+            });
 
         // same as above but for system processes
         let (read_dead, write_dead) = self
@@ -533,6 +792,56 @@ impl AppsContext {
         // remove the dead process from out list of app processes
         self.processes_assigned_to_apps
             .retain(|pid| updated_processes.contains(pid));
+
+        // only needed and kept up to date while grouping by executable
+        if SETTINGS.apps_grouping() == GroupingMode::Executable {
+            self.executable_groups = self.group_by_executable();
+        } else {
+            self.executable_groups.clear();
+        }
+    }
+
+    /// Groups all processes by the binary they're running, see
+    /// `Process::executable_group()`. Groups that already existed keep the I/O
+    /// of their dead processes.
+    fn group_by_executable(&self) -> HashMap<String, App> {
+        let mut groups: HashMap<String, App> = HashMap::new();
+
+        for process in self.all_processes() {
+            let executable = process.executable_group();
+            let id = format!("{EXECUTABLE_ID_PREFIX}{executable}");
+            groups
+                .entry(id)
+                .or_insert_with_key(|id| {
+                    let previous = self.executable_groups.get(id);
+                    // fallbacks to the comm are no paths, even if they contain
+                    // a slash like "kworker/0:1"
+                    let (display_name, description) = match process
+                        .executable_path()
+                        .and_then(|path| path.rsplit_once('/'))
+                    {
+                        Some((_, name)) => (name.to_string(), Some(executable.clone())),
+                        None => (executable.clone(), None),
+                    };
+                    App {
+                        processes: Vec::new(),
+                        commandline: None,
+                        display_name,
+                        description,
+                        // processes belonging to an installed app have its icon
+                        icon: process.icon.clone(),
+                        id: id.clone(),
+                        read_bytes_from_dead_processes: previous
+                            .map_or(0, |group| group.read_bytes_from_dead_processes),
+                        write_bytes_from_dead_processes: previous
+                            .map_or(0, |group| group.write_bytes_from_dead_processes),
+                    }
+                })
+                .processes
+                .push(process.data.pid);
+        }
+
+        groups
     }
 
     pub fn system_processes_iter(&self) -> impl Iterator<Item = &Process> {
@@ -540,3 +849,20 @@ impl AppsContext {
             .filter(|process| !self.processes_assigned_to_apps.contains(&process.data.pid))
     }
 }
+
+/// Sums up the network throughput of the namespaces of `processes`, counting
+/// every namespace only once because its processes share the same counters
+fn net_speed<'a>(processes: impl Iterator<Item = &'a Process>) -> Option<f64> {
+    let mut namespaces = HashSet::new();
+    processes
+        .filter_map(|process| Some((process.data.net_namespace, process.net_speed()?)))
+        .filter(|(namespace, _)| namespaces.insert(*namespace))
+        .fold(None, |sum, (_, speed)| Some(sum.unwrap_or(0.0) + speed))
+}
+
+fn process_state<'a>(processes: impl Iterator<Item = &'a Process>) -> ProcessState {
+    processes
+        .map(|process| ProcessState::from_stat(process.data.state))
+        .max()
+        .unwrap_or_default()
+}
@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+const POWER_SUPPLY_PATH: &str = "/sys/class/power_supply";
+
+/// What a battery is currently doing according to its `status` file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    NotCharging,
+    Full,
+    #[default]
+    Unknown,
+}
+
+impl BatteryStatus {
+    fn from_sysfs<S: AsRef<str>>(status: S) -> Self {
+        match status.as_ref().trim() {
+            "Charging" => BatteryStatus::Charging,
+            "Discharging" => BatteryStatus::Discharging,
+            "Not charging" => BatteryStatus::NotCharging,
+            "Full" => BatteryStatus::Full,
+            _ => BatteryStatus::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BatteryInfo {
+    /// e.g. "BAT0"
+    pub name: String,
+    /// Charge in percent
+    pub capacity: Option<u8>,
+    pub status: BatteryStatus,
+    /// Currently stored energy in µWh
+    pub energy_now: Option<u64>,
+    /// Energy that can be stored when fully charged in µWh
+    pub energy_full: Option<u64>,
+    /// Current charge or discharge rate in µW
+    pub power_now: Option<u64>,
+}
+
+impl BatteryInfo {
+    /// Reads the information about the battery at `sysfs_path`.
+    ///
+    /// Batteries only reporting their charge in µAh (`charge_now` instead of
+    /// `energy_now`) will have `None` for the energy values.
+    pub async fn from_sysfs<P: AsRef<Path>>(sysfs_path: P) -> Self {
+        let sysfs_path = sysfs_path.as_ref();
+
+        let name = sysfs_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let capacity = read_sysfs_value(sysfs_path, "capacity")
+            .await
+            .and_then(|capacity| capacity.parse().ok());
+        let status = read_sysfs_value(sysfs_path, "status")
+            .await
+            .map(BatteryStatus::from_sysfs)
+            .unwrap_or_default();
+        let energy_now = read_sysfs_value(sysfs_path, "energy_now")
+            .await
+            .and_then(|energy| energy.parse().ok());
+        let energy_full = read_sysfs_value(sysfs_path, "energy_full")
+            .await
+            .and_then(|energy| energy.parse().ok());
+        let power_now = read_sysfs_value(sysfs_path, "power_now")
+            .await
+            .and_then(|power| power.parse().ok());
+
+        Self {
+            name,
+            capacity,
+            status,
+            energy_now,
+            energy_full,
+            power_now,
+        }
+    }
+}
+
+async fn read_sysfs_value(sysfs_path: &Path, file: &str) -> Option<String> {
+    tokio::fs::read_to_string(sysfs_path.join(file))
+        .await
+        .ok()
+        .map(|value| value.trim().to_string())
+}
+
+/// Returns the sysfs paths of all power supplies whose name starts with `prefix`
+async fn power_supply_paths(prefix: &str) -> Result<Vec<PathBuf>> {
+    let mut list = Vec::new();
+    let mut entries = tokio::fs::read_dir(POWER_SUPPLY_PATH)
+        .await
+        .with_context(|| format!("unable to read {POWER_SUPPLY_PATH}"))?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_name().to_string_lossy().starts_with(prefix) {
+            list.push(entry.path());
+        }
+    }
+    list.sort();
+    Ok(list)
+}
+
+/// Returns information about all batteries of this system, which is usually
+/// an empty `Vec` on desktops
+///
+/// # Errors
+///
+/// Will return `Err` if /sys/class/power_supply can't be read
+pub async fn battery_info() -> Result<Vec<BatteryInfo>> {
+    let mut batteries = Vec::new();
+    for path in power_supply_paths("BAT").await? {
+        batteries.push(BatteryInfo::from_sysfs(path).await);
+    }
+    Ok(batteries)
+}
+
+/// Returns whether the system is connected to AC power or `None` if there is
+/// no AC adapter to ask (e.g. on desktops)
+pub async fn ac_online() -> Option<bool> {
+    let mut online = None;
+    for path in power_supply_paths("AC").await.ok()? {
+        if let Some(adapter_online) = read_sysfs_value(&path, "online").await {
+            online = Some(online.unwrap_or(false) || adapter_online == "1");
+        }
+    }
+    online
+}
@@ -1,17 +1,53 @@
 use anyhow::{anyhow, bail, Context, Result};
 use async_process::Command;
 use glob::glob;
+use nix::time::{clock_gettime, ClockId};
 use nparse::KVStrToJson;
 use once_cell::sync::Lazy;
 use regex::bytes::Regex;
+use serde::Serialize;
 use serde_json::Value;
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
+use std::time::{Duration, SystemTime};
+
+use crate::i18n::i18n_f;
+
+use super::units::convert_frequency;
 
 static PROC_STAT_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"cpu[0-9]* *(?P<user>[0-9]*) *(?P<nice>[0-9]*) *(?P<system>[0-9]*) *(?P<idle>[0-9]*) *(?P<iowait>[0-9]*) *(?P<irq>[0-9]*) *(?P<softirq>[0-9]*) *(?P<steal>[0-9]*) *(?P<guest>[0-9]*) *(?P<guest_nice>[0-9]*)").unwrap()
 });
 
+/// lscpu keys for the vendor and the model on architectures that don't use
+/// the x86-style "Vendor ID" and "Model name", keyed by the start of the
+/// "Architecture" field
+const LSCPU_FALLBACK_KEYS: &[(&str, &[&str], &[&str])] = &[
+    ("aarch64", &["CPU implementer"], &["Model"]),
+    ("arm", &["CPU implementer"], &["Model"]),
+    ("riscv", &["mvendorid"], &["uarch", "Model"]),
+];
+
+/// Names of ARM CPU implementers as reported by "CPU implementer" in lscpu
+const ARM_IMPLEMENTERS: &[(&str, &str)] = &[
+    ("0x41", "ARM"),
+    ("0x42", "Broadcom"),
+    ("0x43", "Cavium"),
+    ("0x46", "Fujitsu"),
+    ("0x48", "HiSilicon"),
+    ("0x4e", "NVIDIA"),
+    ("0x50", "APM"),
+    ("0x51", "Qualcomm"),
+    ("0x53", "Samsung"),
+    ("0x61", "Apple"),
+    ("0x69", "Intel"),
+    ("0x6d", "Microsoft"),
+    ("0xc0", "Ampere"),
+];
+
+static CPU_INFO: OnceLock<CPUInfo> = OnceLock::new();
+
 static ZENPOWER: OnceLock<PathBuf> = OnceLock::new();
 static CORETEMP: OnceLock<PathBuf> = OnceLock::new();
 static K10TEMP: OnceLock<PathBuf> = OnceLock::new();
@@ -19,55 +55,146 @@ static X86_PKG_TEMP: OnceLock<PathBuf> = OnceLock::new();
 static ACPI: OnceLock<PathBuf> = OnceLock::new();
 
 pub struct CpuData {
-    pub new_total_usage: (u64, u64),
+    pub new_total_usage: Result<(u64, u64), anyhow::Error>,
+    pub new_total_breakdown: Result<CpuTimes, anyhow::Error>,
     pub new_thread_usages: Vec<(u64, u64)>,
     pub temperature: Result<f32, anyhow::Error>,
-    pub frequencies: Vec<u64>,
+    /// `None` for cores whose frequency is unknown, e.g. because they're offline
+    pub frequencies: Vec<Option<u64>>,
+    pub throttle_counts: Option<ThrottleCounts>,
 }
 
 impl CpuData {
     pub async fn new(logical_cpus: usize) -> Self {
-        let new_total_usage =
-            tokio::spawn(async move { get_cpu_usage(None).await.unwrap_or((0, 0)) });
+        let new_total_usage = tokio::spawn(async move { get_cpu_usage(None).await });
 
-        let temperature = tokio::spawn(async move { get_temperature().await });
+        let new_total_breakdown = tokio::spawn(async move { get_cpu_breakdown(None).await });
 
-        let mut freq_tasks = vec![];
-        let mut new_thread_usages_tasks = vec![];
-        for i in 0..logical_cpus {
-            let handle =
-                tokio::spawn(async move { get_cpu_usage(Some(i)).await.unwrap_or((0, 0)) });
-            new_thread_usages_tasks.push(handle);
+        let temperature = tokio::spawn(async move { get_temperature().await });
 
-            let handle = tokio::spawn(async move { get_cpu_freq(i).await.unwrap_or(0) });
-            freq_tasks.push(handle);
-        }
+        let new_thread_usages = tokio::spawn(async move {
+            let mut usages = get_all_cpu_usages().await.unwrap_or_default();
+            usages.resize(logical_cpus, (0, 0));
+            usages
+        });
 
-        let mut frequencies = vec![];
-        for task in freq_tasks {
-            let freq = task.await.unwrap();
-            frequencies.push(freq);
-        }
+        let frequencies = get_all_cpu_freqs(logical_cpus).await;
 
-        let mut new_thread_usages = vec![];
-        for task in new_thread_usages_tasks {
-            let new_thread_usage = task.await.unwrap();
-            new_thread_usages.push(new_thread_usage);
-        }
+        let throttle_counts = get_throttle_counts().await;
 
+        let new_thread_usages = new_thread_usages.await.unwrap();
         let new_total_usage = new_total_usage.await.unwrap();
+        let new_total_breakdown = new_total_breakdown.await.unwrap();
         let temperature = temperature.await.unwrap();
 
         Self {
             new_total_usage,
+            new_total_breakdown,
             new_thread_usages,
             temperature,
             frequencies,
+            throttle_counts,
+        }
+    }
+}
+
+/// How often the CPU has been thermally throttled since boot
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ThrottleCounts {
+    /// Summed up over all cores
+    pub core: u64,
+    /// Summed up over all packages (sockets)
+    pub package: u64,
+}
+
+impl ThrottleCounts {
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.core + self.package
+    }
+
+    /// Whether throttling has happened between `earlier` and `self`
+    #[must_use]
+    pub fn throttled_since(&self, earlier: &Self) -> bool {
+        self.core > earlier.core || self.package > earlier.package
+    }
+}
+
+/// The CPU time since boot split up by what it was spent on, in the units of
+/// /proc/stat (usually 1/100th of a second)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+    /// Time spent running virtual machines, this is already included in `user`
+    pub guest: u64,
+}
+
+impl CpuTimes {
+    /// Returns the sum of all times, without counting `guest` twice
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.user
+            + self.nice
+            + self.system
+            + self.idle
+            + self.iowait
+            + self.irq
+            + self.softirq
+            + self.steal
+    }
+
+    /// Returns the times that have passed between `earlier` and `self`
+    #[must_use]
+    pub fn saturating_sub(&self, earlier: &Self) -> Self {
+        Self {
+            user: self.user.saturating_sub(earlier.user),
+            nice: self.nice.saturating_sub(earlier.nice),
+            system: self.system.saturating_sub(earlier.system),
+            idle: self.idle.saturating_sub(earlier.idle),
+            iowait: self.iowait.saturating_sub(earlier.iowait),
+            irq: self.irq.saturating_sub(earlier.irq),
+            softirq: self.softirq.saturating_sub(earlier.softirq),
+            steal: self.steal.saturating_sub(earlier.steal),
+            guest: self.guest.saturating_sub(earlier.guest),
         }
     }
 }
 
-#[derive(Debug, Clone, Default)]
+/// System-wide counters from the non-CPU lines of /proc/stat
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SystemStat {
+    /// Context switches since boot (`ctxt`)
+    pub context_switches: u64,
+    /// Processes and threads created since boot (`processes`)
+    pub processes_created: u64,
+    /// Threads that are currently runnable (`procs_running`)
+    pub processes_running: u64,
+    /// Threads that are currently waiting for I/O (`procs_blocked`)
+    pub processes_blocked: u64,
+    /// Seconds since the Unix epoch at which the system booted (`btime`)
+    pub boot_time: u64,
+}
+
+impl SystemStat {
+    /// Converts a time since boot, e.g. `Process::start_time()`, to the wall
+    /// clock time it corresponds to
+    #[must_use]
+    pub fn since_boot_to_wall_clock(&self, since_boot: Duration) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(self.boot_time) + since_boot
+    }
+}
+
+/// The field names are part of the output of `cpu_info_json()`, so renaming
+/// a field must keep its serialized name
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub struct CPUInfo {
     pub vendor_id: Option<String>,
     pub model_name: Option<String>,
@@ -77,6 +204,23 @@ pub struct CPUInfo {
     pub sockets: Option<usize>,
     pub virtualization: Option<String>,
     pub max_speed: Option<f32>,
+    /// Either "Little Endian" or "Big Endian"
+    pub byte_order: Option<String>,
+    /// e.g. "39 bits physical, 48 bits virtual", usually only known on x86
+    pub address_sizes: Option<String>,
+    /// Cache sizes in bytes, summed up over all instances if `lscpu` is new
+    /// enough to report it that way
+    pub l1d_cache: Option<usize>,
+    pub l1i_cache: Option<usize>,
+    pub l2_cache: Option<usize>,
+    pub l3_cache: Option<usize>,
+    pub numa_nodes: Option<usize>,
+    /// The logical CPUs belonging to each NUMA node, keyed by node since
+    /// node numbers don't have to be contiguous
+    pub numa_node_cpus: BTreeMap<usize, Vec<usize>>,
+    /// Supported features and instruction sets (e.g. "avx2" or "aes"),
+    /// sorted alphabetically
+    pub flags: Vec<String>,
 }
 
 async fn lscpu() -> Result<Value> {
@@ -93,24 +237,298 @@ async fn lscpu() -> Result<Value> {
     .map_err(|x| anyhow!("{}", x))
 }
 
+/// Returns the value of the first of `keys` that is present in `lscpu_output`
+fn lscpu_value(lscpu_output: &Value, keys: &[&str]) -> Option<String> {
+    keys.iter()
+        .find_map(|key| lscpu_output[*key].as_str())
+        .map(std::string::ToString::to_string)
+}
+
+/// Converts sizes like "32K", "512 KiB" or "32 MiB (2 instances)" as emitted
+/// by different versions of `lscpu` to bytes. Units are always interpreted as
+/// binary prefixes since that's what `lscpu` uses even without the "i".
+fn parse_size_string<S: AsRef<str>>(size: S) -> Option<usize> {
+    let size = size.as_ref().split('(').next()?.trim();
+    let unit_start = size
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(size.len());
+    let (number, unit) = size.split_at(unit_start);
+    let number = number.parse::<f64>().ok()?;
+
+    let multiplier: f64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1.0,
+        "K" | "KB" | "KIB" => 1024.0,
+        "M" | "MB" | "MIB" => 1024.0 * 1024.0,
+        "G" | "GB" | "GIB" => 1024.0 * 1024.0 * 1024.0,
+        _ => return None,
+    };
+
+    Some((number * multiplier).round() as usize)
+}
+
+/// Expands CPU lists like "0-7,16-23" or "0,2,4" as used by `lscpu` and sysfs
+/// into the individual CPU numbers, returns `None` if `list` is malformed
+fn parse_cpu_list<S: AsRef<str>>(list: S) -> Option<Vec<usize>> {
+    let mut cpus = Vec::new();
+
+    for part in list
+        .as_ref()
+        .trim()
+        .split(',')
+        .filter(|part| !part.is_empty())
+    {
+        if let Some((start, end)) = part.split_once('-') {
+            let start = start.trim().parse::<usize>().ok()?;
+            let end = end.trim().parse::<usize>().ok()?;
+            cpus.extend(start..=end);
+        } else {
+            cpus.push(part.trim().parse().ok()?);
+        }
+    }
+
+    Some(cpus)
+}
+
+/// Returns the CPUs of every NUMA node listed in `lscpu_output`. The nodes are
+/// taken from the keys since e.g. node 1 may be missing while node 2 exists.
+fn numa_node_cpus(lscpu_output: &Value) -> BTreeMap<usize, Vec<usize>> {
+    lscpu_output
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| {
+            let node = key
+                .strip_prefix("NUMA node")?
+                .strip_suffix(" CPU(s)")?
+                .parse()
+                .ok()?;
+            Some((node, value.as_str().and_then(parse_cpu_list)?))
+        })
+        .collect()
+}
+
+/// Returns the name of the hardware virtualization extension advertised in
+/// the "flags" of `cpuinfo` (the contents of /proc/cpuinfo), if any
+fn virtualization_from_cpuinfo(cpuinfo: &str) -> Option<String> {
+    let flags = cpuinfo
+        .lines()
+        .find(|line| line.starts_with("flags"))?
+        .split_once(':')?
+        .1;
+
+    flags.split_whitespace().find_map(|flag| match flag {
+        "vmx" => Some("VT-x".to_string()),
+        "svm" => Some("AMD-V".to_string()),
+        _ => None,
+    })
+}
+
+/// Splits a whitespace-separated list of CPU flags into a sorted list
+fn parse_flags(flags: &str) -> Vec<String> {
+    let mut flags = flags
+        .split_whitespace()
+        .map(std::string::ToString::to_string)
+        .collect::<Vec<_>>();
+    flags.sort_unstable();
+    flags.dedup();
+    flags
+}
+
+/// Returns the flags of the first CPU in /proc/cpuinfo, which are called
+/// "flags" on x86 and "Features" on ARM
+fn flags_from_cpuinfo(cpuinfo: &str) -> Vec<String> {
+    cpuinfo
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim(), "flags" | "Features").then(|| parse_flags(value))
+        })
+        .unwrap_or_default()
+}
+
 /// Returns a `CPUInfo` struct populated with values gathered from `lscpu`.
 ///
+/// `lscpu` is only run until it succeeded once, since none of these values
+/// change at runtime. Use `cpu_info_uncached()` to bypass the cache.
+///
 /// # Errors
 ///
 /// Will return `Err` if the are problems during reading or parsing
 /// of the `lscpu` command
 pub async fn cpu_info() -> Result<CPUInfo> {
+    if let Some(cpu_info) = CPU_INFO.get() {
+        return Ok(cpu_info.clone());
+    }
+
+    let cpu_info = cpu_info_uncached().await?;
+    Ok(CPU_INFO.get_or_init(|| cpu_info).clone())
+}
+
+/// Returns the (cached) `CPUInfo` as pretty-printed JSON, e.g. for scripts
+/// that want to capture the parsed topology without the GUI
+///
+/// # Errors
+///
+/// Will return `Err` if the `CPUInfo` couldn't be gathered, see `cpu_info()`
+pub async fn cpu_info_json() -> Result<String> {
+    serde_json::to_string_pretty(&cpu_info().await?).with_context(|| "unable to serialize CPUInfo")
+}
+
+/// Formats the known fields of `info` as labeled lines of plain text, e.g.
+/// for pasting them into bug reports. Unknown fields are left out.
+#[must_use]
+pub fn cpu_info_to_text(info: &CPUInfo) -> String {
+    let cores = match (info.physical_cpus, info.logical_cpus) {
+        (Some(physical), Some(logical)) => Some(format!("{physical}/{logical}")),
+        (Some(physical), None) => Some(physical.to_string()),
+        (None, Some(logical)) => Some(format!("?/{logical}")),
+        (None, None) => None,
+    };
+
+    [
+        info.model_name
+            .as_ref()
+            .map(|model| i18n_f("Model: {}", &[model])),
+        info.vendor_id
+            .as_ref()
+            .map(|vendor| i18n_f("Vendor: {}", &[vendor])),
+        info.architecture
+            .as_ref()
+            .map(|architecture| i18n_f("Architecture: {}", &[architecture])),
+        cores.map(|cores| i18n_f("Cores/Threads: {}", &[&cores])),
+        info.sockets
+            .map(|sockets| i18n_f("Sockets: {}", &[&sockets.to_string()])),
+        info.max_speed
+            .map(|max_speed| i18n_f("Max Speed: {}", &[&convert_frequency(f64::from(max_speed))])),
+        info.virtualization
+            .as_ref()
+            .map(|virtualization| i18n_f("Virtualization: {}", &[virtualization])),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+/// Returns a `CPUInfo` struct populated with values freshly gathered from
+/// `lscpu`, or from /proc/cpuinfo if `lscpu` is unavailable.
+///
+/// # Errors
+///
+/// Will return `Err` if neither `lscpu` nor /proc/cpuinfo could be read
+pub async fn cpu_info_uncached() -> Result<CPUInfo> {
+    match cpu_info_from_lscpu().await {
+        Ok(cpu_info) => Ok(cpu_info),
+        Err(error) => {
+            log::warn!("{error}, falling back to /proc/cpuinfo");
+            cpu_info_from_proc().await
+        }
+    }
+}
+
+/// Returns a `CPUInfo` struct with the subset of values that can be gathered
+/// from /proc/cpuinfo, the rest stays `None`
+///
+/// # Errors
+///
+/// Will return `Err` if /proc/cpuinfo could not be read
+pub async fn cpu_info_from_proc() -> Result<CPUInfo> {
+    let cpuinfo = tokio::fs::read_to_string("/proc/cpuinfo")
+        .await
+        .with_context(|| "unable to read /proc/cpuinfo")?;
+
+    let value = |keys: &[&str]| {
+        cpuinfo.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            keys.contains(&key.trim())
+                .then(|| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        })
+    };
+
+    let vendor_id = value(&["vendor_id", "CPU implementer"]).map(|vendor_id| {
+        ARM_IMPLEMENTERS
+            .iter()
+            .find(|(implementer, _)| implementer.eq_ignore_ascii_case(&vendor_id))
+            .map_or(vendor_id, |(_, name)| (*name).to_string())
+    });
+    let model_name = value(&["model name", "Model", "cpu model", "uarch"]);
+
+    // every logical CPU has its own block in /proc/cpuinfo
+    let mut logical_cpus = 0;
+    let mut sockets = HashSet::new();
+    let mut cores = HashSet::new();
+    for block in cpuinfo.split("\n\n") {
+        let field = |key: &str| {
+            block.lines().find_map(|line| {
+                let (line_key, value) = line.split_once(':')?;
+                (line_key.trim() == key).then(|| value.trim().to_string())
+            })
+        };
+        if field("processor").is_none() {
+            continue;
+        }
+        logical_cpus += 1;
+        if let Some(physical_id) = field("physical id") {
+            if let Some(core_id) = field("core id") {
+                cores.insert((physical_id.clone(), core_id));
+            }
+            sockets.insert(physical_id);
+        }
+    }
+
+    Ok(CPUInfo {
+        vendor_id,
+        model_name,
+        architecture: Some(std::env::consts::ARCH.to_string()),
+        logical_cpus: (logical_cpus > 0).then_some(logical_cpus),
+        physical_cpus: (!cores.is_empty()).then_some(cores.len()),
+        sockets: (!sockets.is_empty()).then_some(sockets.len()),
+        virtualization: virtualization_from_cpuinfo(&cpuinfo),
+        // this is what lscpu does as well
+        byte_order: Some(if cfg!(target_endian = "big") {
+            "Big Endian".to_string()
+        } else {
+            "Little Endian".to_string()
+        }),
+        address_sizes: value(&["address sizes"]),
+        flags: flags_from_cpuinfo(&cpuinfo),
+        ..Default::default()
+    })
+}
+
+async fn cpu_info_from_lscpu() -> Result<CPUInfo> {
     let lscpu_output = lscpu().await?;
 
-    let vendor_id = lscpu_output["Vendor ID"]
+    let architecture = lscpu_output["Architecture"]
         .as_str()
         .map(std::string::ToString::to_string);
-    let model_name = lscpu_output["Model name"]
+
+    let (vendor_fallback_keys, model_fallback_keys) = architecture
+        .as_deref()
+        .and_then(|architecture| {
+            LSCPU_FALLBACK_KEYS
+                .iter()
+                .find(|(prefix, _, _)| architecture.starts_with(prefix))
+        })
+        .map_or((&[][..], &[][..]), |(_, vendor_keys, model_keys)| {
+            (*vendor_keys, *model_keys)
+        });
+
+    let vendor_id = lscpu_output["Vendor ID"]
         .as_str()
-        .map(std::string::ToString::to_string);
-    let architecture = lscpu_output["Architecture"]
+        .map(std::string::ToString::to_string)
+        .or_else(|| lscpu_value(&lscpu_output, vendor_fallback_keys))
+        .map(|vendor_id| {
+            ARM_IMPLEMENTERS
+                .iter()
+                .find(|(implementer, _)| implementer.eq_ignore_ascii_case(&vendor_id))
+                .map_or(vendor_id, |(_, name)| (*name).to_string())
+        });
+    let model_name = lscpu_output["Model name"]
         .as_str()
-        .map(std::string::ToString::to_string);
+        .map(std::string::ToString::to_string)
+        .or_else(|| lscpu_value(&lscpu_output, model_fallback_keys));
     let logical_cpus = lscpu_output["CPU(s)"]
         .as_str()
         .and_then(|x| x.parse::<usize>().ok());
@@ -120,13 +538,50 @@ pub async fn cpu_info() -> Result<CPUInfo> {
     let physical_cpus = lscpu_output["Core(s) per socket"]
         .as_str()
         .and_then(|x| x.parse::<usize>().ok().map(|y| y * sockets.unwrap_or(1)));
-    let virtualization = lscpu_output["Virtualization"]
-        .as_str()
-        .map(std::string::ToString::to_string);
+    let virtualization = match lscpu_output["Virtualization"].as_str() {
+        Some(virtualization) => Some(virtualization.to_string()),
+        None => tokio::fs::read_to_string("/proc/cpuinfo")
+            .await
+            .ok()
+            .and_then(|cpuinfo| virtualization_from_cpuinfo(&cpuinfo)),
+    };
     let max_speed = lscpu_output["CPU max MHz"]
         .as_str()
         .and_then(|x| x.parse::<f32>().ok())
         .map(|y| y * 1_000_000.0);
+    let byte_order = lscpu_output["Byte Order"]
+        .as_str()
+        .map(std::string::ToString::to_string);
+    let address_sizes = lscpu_output["Address sizes"]
+        .as_str()
+        .map(std::string::ToString::to_string);
+    let l1d_cache = lscpu_output["L1d cache"]
+        .as_str()
+        .and_then(parse_size_string);
+    let l1i_cache = lscpu_output["L1i cache"]
+        .as_str()
+        .and_then(parse_size_string);
+    let l2_cache = lscpu_output["L2 cache"]
+        .as_str()
+        .and_then(parse_size_string);
+    let l3_cache = lscpu_output["L3 cache"]
+        .as_str()
+        .and_then(parse_size_string);
+    let numa_nodes = lscpu_output["NUMA node(s)"]
+        .as_str()
+        .and_then(|x| x.parse::<usize>().ok());
+    let numa_node_cpus = numa_node_cpus(&lscpu_output);
+    // lscpu only prints the flags on some architectures
+    let mut flags = lscpu_output["Flags"]
+        .as_str()
+        .map(parse_flags)
+        .unwrap_or_default();
+    if flags.is_empty() {
+        flags = tokio::fs::read_to_string("/proc/cpuinfo")
+            .await
+            .map(|cpuinfo| flags_from_cpuinfo(&cpuinfo))
+            .unwrap_or_default();
+    }
 
     Ok(CPUInfo {
         vendor_id,
@@ -137,6 +592,15 @@ pub async fn cpu_info() -> Result<CPUInfo> {
         sockets,
         virtualization,
         max_speed,
+        byte_order,
+        address_sizes,
+        l1d_cache,
+        l1i_cache,
+        l2_cache,
+        l3_cache,
+        numa_nodes,
+        numa_node_cpus,
+        flags,
     })
 }
 
@@ -158,6 +622,40 @@ pub async fn get_cpu_freq(core: usize) -> Result<u64> {
     .map(|x| x * 1000)
 }
 
+/// Returns the frequencies of the first `logical_cpus` CPU cores, indexed by
+/// core. Cores whose frequency can't be read (e.g. because they're offline
+/// due to CPU hotplugging or disabled SMT) are `None`.
+pub async fn get_all_cpu_freqs(logical_cpus: usize) -> Vec<Option<u64>> {
+    let tasks = (0..logical_cpus)
+        .map(|core| tokio::spawn(async move { get_cpu_freq(core).await.ok() }))
+        .collect::<Vec<_>>();
+
+    let mut frequencies = Vec::with_capacity(logical_cpus);
+    for task in tasks {
+        frequencies.push(task.await.ok().flatten());
+    }
+    frequencies
+}
+
+/// Returns the average of the frequencies that could be read, or `None` if
+/// none of them could be read
+pub fn average_cpu_freq(frequencies: &[Option<u64>]) -> Option<u64> {
+    let known = frequencies.iter().flatten().collect::<Vec<_>>();
+    if known.is_empty() {
+        return None;
+    }
+    Some(known.iter().copied().sum::<u64>() / known.len() as u64)
+}
+
+/// Returns how close `current` is to `max` as a value between 0.0 and 1.0.
+/// Both are expected in Hz, like they're returned by `get_cpu_freq` and stored
+/// in `CPUInfo::max_speed`. Returns `None` if the maximum frequency is unknown,
+/// which is common in VMs.
+pub fn cpu_freq_ratio(current: u64, max: Option<f32>) -> Option<f32> {
+    max.filter(|max| *max > 0.0)
+        .map(|max| (current as f32 / max).clamp(0.0, 1.0))
+}
+
 fn parse_proc_stat_line(line: &[u8]) -> Result<(u64, u64)> {
     let captures = PROC_STAT_REGEX
         .captures(line)
@@ -181,20 +679,113 @@ fn parse_proc_stat_line(line: &[u8]) -> Result<(u64, u64)> {
     Ok((idle_time, sum))
 }
 
+fn parse_proc_stat_times(line: &[u8]) -> Result<CpuTimes> {
+    let captures = PROC_STAT_REGEX
+        .captures(line)
+        .ok_or_else(|| anyhow!("using regex to parse /proc/stat failed"))?;
+    let field = |name: &str| {
+        captures
+            .name(name)
+            .and_then(|x| String::from_utf8_lossy(x.as_bytes()).parse::<u64>().ok())
+            .with_context(|| format!("unable to get {name} time"))
+    };
+    Ok(CpuTimes {
+        user: field("user")?,
+        nice: field("nice")?,
+        system: field("system")?,
+        idle: field("idle")?,
+        iowait: field("iowait")?,
+        irq: field("irq")?,
+        softirq: field("softirq")?,
+        steal: field("steal")?,
+        guest: field("guest")?,
+    })
+}
+
+/// Returns the system-wide counters of /proc/stat, see `SystemStat`
+///
+/// # Errors
+///
+/// Will return `Err` if /proc/stat couldn't be read or one of the lines is
+/// missing or malformed
+pub async fn get_system_stat() -> Result<SystemStat> {
+    let proc_stat = tokio::fs::read_to_string("/proc/stat")
+        .await
+        .with_context(|| "unable to read /proc/stat")?;
+    parse_system_stat(&proc_stat)
+}
+
+fn parse_system_stat(proc_stat: &str) -> Result<SystemStat> {
+    let mut context_switches = None;
+    let mut processes_created = None;
+    let mut processes_running = None;
+    let mut processes_blocked = None;
+    let mut boot_time = None;
+
+    for line in proc_stat.lines() {
+        let mut fields = line.split_whitespace();
+        let field = match fields.next() {
+            Some("ctxt") => &mut context_switches,
+            Some("processes") => &mut processes_created,
+            Some("procs_running") => &mut processes_running,
+            Some("procs_blocked") => &mut processes_blocked,
+            Some("btime") => &mut boot_time,
+            _ => continue,
+        };
+        *field = fields.next().and_then(|value| value.parse::<u64>().ok());
+    }
+
+    Ok(SystemStat {
+        context_switches: context_switches.context("unable to get ctxt from /proc/stat")?,
+        processes_created: processes_created.context("unable to get processes from /proc/stat")?,
+        processes_running: processes_running
+            .context("unable to get procs_running from /proc/stat")?,
+        processes_blocked: processes_blocked
+            .context("unable to get procs_blocked from /proc/stat")?,
+        boot_time: boot_time.context("unable to get btime from /proc/stat")?,
+    })
+}
+
 async fn get_proc_stat(core: Option<usize>) -> Result<String> {
-    // the combined stats are in line 0, the other cores are in the following lines,
-    // since our `core` argument starts with 0, we must add 1 to it if it's not `None`.
-    let selected_line_number = core.map_or(0, |x| x + 1);
     let proc_stat_raw = tokio::fs::read_to_string("/proc/stat")
         .await
         .with_context(|| "unable to read /proc/stat")?;
-    let mut proc_stat = proc_stat_raw.split('\n').collect::<Vec<&str>>();
-    proc_stat.retain(|x| x.starts_with("cpu"));
-    // return an `Error` if `core` is greater than the number of cores
-    if selected_line_number >= proc_stat.len() {
-        bail!("`core` argument greater than amount of cores")
-    }
-    Ok(proc_stat[selected_line_number].to_string())
+    proc_stat_line(&proc_stat_raw, core).map(ToString::to_string)
+}
+
+/// Finds the line of `core` (or the combined `cpu` line if `None`) in the
+/// contents of /proc/stat. Offline cores have no line, so the line's position
+/// doesn't necessarily match the core's number and the `cpuN` prefix is used
+/// instead.
+fn proc_stat_line(proc_stat: &str, core: Option<usize>) -> Result<&str> {
+    let name = core.map_or_else(|| "cpu".to_string(), |core| format!("cpu{core}"));
+    proc_stat
+        .lines()
+        .find(|line| line.split_whitespace().next() == Some(name.as_str()))
+        .with_context(|| format!("no {name} line in /proc/stat, the core may be offline"))
+}
+
+/// If more time than this multiple of the refresh interval passed between two
+/// refreshes, the machine has most likely been suspended in between
+const SUSPEND_INTERVAL_FACTOR: f32 = 2.0;
+
+/// Time since boot including time spent suspended, unlike the monotonic
+/// clock that glib's timeouts and `Instant` use
+#[must_use]
+pub fn boot_clock() -> Option<Duration> {
+    clock_gettime(ClockId::CLOCK_BOOTTIME)
+        .ok()
+        .map(Duration::from)
+}
+
+/// Whether the machine has presumably been suspended between two refreshes
+/// that took place at `last` and `now` (see `boot_clock()`). The refresh
+/// loop doesn't run while suspended, so the time between the two refreshes
+/// is much longer than `interval` and the CPU counters may have jumped in the
+/// meantime, making the deltas between them meaningless.
+#[must_use]
+pub fn resumed_from_suspend(last: Duration, now: Duration, interval: f32) -> bool {
+    now.saturating_sub(last).as_secs_f32() > interval * SUSPEND_INTERVAL_FACTOR
 }
 
 /// Returns the CPU usage of either all cores combined (if supplied argument is `None`),
@@ -210,6 +801,117 @@ pub async fn get_cpu_usage(core: Option<usize>) -> Result<(u64, u64)> {
     parse_proc_stat_line(get_proc_stat(core).await?.as_bytes())
 }
 
+/// Like `get_cpu_usage()`, but returns the individual times instead of only
+/// the idle and total time, e.g. to tell I/O wait or steal time apart
+///
+/// # Errors
+///
+/// Will return `Err` if the are problems during reading or parsing
+/// of /proc/stat
+pub async fn get_cpu_breakdown(core: Option<usize>) -> Result<CpuTimes> {
+    parse_proc_stat_times(get_proc_stat(core).await?.as_bytes())
+}
+
+/// Returns the CPU usages of all logical cores, read from a single pass over
+/// /proc/stat. The combined `cpu` line is skipped, so index `i` of the returned
+/// `Vec` belongs to core `i`. Cores missing from /proc/stat (e.g. because they're
+/// offline) are reported as `(0, 0)`.
+/// Like in `get_cpu_usage()`, this is the total CPU time since boot and the
+/// tuples' layout is `(idle_time, total_time)`
+///
+/// # Errors
+///
+/// Will return `Err` if the are problems during reading or parsing
+/// of /proc/stat
+pub async fn get_all_cpu_usages() -> Result<Vec<(u64, u64)>> {
+    let proc_stat_raw = tokio::fs::read_to_string("/proc/stat")
+        .await
+        .with_context(|| "unable to read /proc/stat")?;
+
+    let mut usages = Vec::new();
+    for line in proc_stat_raw.split('\n') {
+        let Some(core) = line
+            .split_whitespace()
+            .next()
+            .and_then(|name| name.strip_prefix("cpu"))
+            .and_then(|core| core.parse::<usize>().ok())
+        else {
+            continue;
+        };
+
+        if core >= usages.len() {
+            usages.resize(core + 1, (0, 0));
+        }
+        usages[core] = parse_proc_stat_line(line.as_bytes())?;
+    }
+
+    Ok(usages)
+}
+
+/// Returns the amount of CPUs the cgroup (v2) Resources is running in is
+/// allowed to use, e.g. `1.5` for a `cpu.max` of `150000 100000`.
+///
+/// Returns `None` if there is no quota or it couldn't be read.
+#[must_use]
+pub fn get_cpu_quota() -> Option<f32> {
+    let cpu_max = std::fs::read_to_string("/sys/fs/cgroup/cpu.max").ok()?;
+    let mut split = cpu_max.split_whitespace();
+
+    let quota = split.next()?.parse::<f32>().ok()?;
+    let period = split.next().unwrap_or("100000").parse::<f32>().ok()?;
+
+    if period > 0.0 {
+        Some(quota / period)
+    } else {
+        None
+    }
+}
+
+async fn read_throttle_count(path: &Path) -> Option<u64> {
+    tokio::fs::read_to_string(path)
+        .await
+        .ok()
+        .and_then(|count| count.trim().parse().ok())
+}
+
+/// Returns how often the CPU has been thermally throttled since boot, read from
+/// /sys/devices/system/cpu/cpu*/thermal_throttle.
+///
+/// Returns `None` if the kernel doesn't report this, which is the case for
+/// everything but Intel CPUs.
+pub async fn get_throttle_counts() -> Option<ThrottleCounts> {
+    let mut counts = ThrottleCounts::default();
+    let mut counted_packages = HashSet::new();
+    let mut found = false;
+
+    for path in glob("/sys/devices/system/cpu/cpu[0-9]*/thermal_throttle")
+        .ok()?
+        .flatten()
+    {
+        if let Some(core_count) = read_throttle_count(&path.join("core_throttle_count")).await {
+            counts.core += core_count;
+            found = true;
+        }
+
+        // every core reports the count of its package, so only count each
+        // package once
+        let package_id =
+            tokio::fs::read_to_string(path.with_file_name("topology").join("physical_package_id"))
+                .await
+                .unwrap_or_default();
+        if counted_packages.insert(package_id.trim().to_string()) {
+            if let Some(package_count) =
+                read_throttle_count(&path.join("package_throttle_count")).await
+            {
+                counts.package += package_count;
+                found = true;
+            }
+        }
+    }
+
+    found.then_some(counts)
+}
+
 /// Returns the CPU temperature.
 ///
 /// # Errors
@@ -270,6 +972,53 @@ pub async fn get_temperature() -> Result<f32> {
     bail!("no CPU temperature sensor found")
 }
 
+/// Names of the hwmons that report the CPU temperature, in the order of
+/// preference
+const CPU_HWMON_NAMES: &[&str] = &["zenpower", "k10temp", "coretemp"];
+
+/// Returns the CPU temperature in °C as reported by the hwmons in
+/// /sys/class/hwmon, without falling back to thermal zones like
+/// `get_temperature()`
+///
+/// # Errors
+///
+/// Will return `Err` if there is no CPU hwmon or its temperature can't be read
+pub fn get_cpu_temperature() -> Result<f32> {
+    cpu_temperature_from(Path::new("/sys/class/hwmon"))
+}
+
+/// Looks for a CPU hwmon in `hwmon_base` and reads its temp1_input, which is
+/// given in millidegrees Celsius
+fn cpu_temperature_from(hwmon_base: &Path) -> Result<f32> {
+    let hwmons = std::fs::read_dir(hwmon_base)
+        .with_context(|| format!("unable to read {}", hwmon_base.display()))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("hwmon"))
+        })
+        .filter_map(|path| {
+            let name = std::fs::read_to_string(path.join("name")).ok()?;
+            Some((name.trim().to_string(), path))
+        })
+        .collect::<Vec<_>>();
+
+    let path = CPU_HWMON_NAMES
+        .iter()
+        .find_map(|cpu_name| hwmons.iter().find(|(name, _)| name == cpu_name))
+        .map(|(_, path)| path.join("temp1_input"))
+        .with_context(|| format!("no CPU hwmon found in {}", hwmon_base.display()))?;
+
+    std::fs::read_to_string(&path)
+        .with_context(|| format!("unable to read {}", path.display()))?
+        .trim()
+        .parse::<f32>()
+        .with_context(|| format!("unable to parse {}", path.display()))
+        .map(|t| t / 1000f32)
+}
+
 async fn read_sysfs_thermal(path: &PathBuf) -> Result<f32> {
     let temp_string = tokio::fs::read_to_string(path)
         .await
@@ -280,3 +1029,301 @@ async fn read_sysfs_thermal(path: &PathBuf) -> Result<f32> {
         .with_context(|| format!("unable to parse {}", path.display()))
         .map(|t| t / 1000f32)
 }
+
+/// Returns the index of the thermal zone that is used for the CPU temperature
+/// (if any), e.g. `Some(2)` for /sys/class/thermal/thermal_zone2
+///
+/// Returns `None` if the CPU temperature is read from a hwmon or if
+/// `get_temperature()` has not been called yet
+pub fn cpu_thermal_zone() -> Option<usize> {
+    if ZENPOWER.get().is_some() || K10TEMP.get().is_some() || CORETEMP.get().is_some() {
+        return None;
+    }
+
+    X86_PKG_TEMP
+        .get()
+        .or_else(|| ACPI.get())
+        .and_then(|path| path.parent())
+        .and_then(|zone_path| zone_path.file_name())
+        .and_then(|name| name.to_str())
+        .and_then(|name| name.strip_prefix("thermal_zone"))
+        .and_then(|index| index.parse().ok())
+}
+
+/// Returns the trip points of the thermal zone `zone` as pairs of their type
+/// (e.g. "critical" or "passive") and their temperature in °C, sorted by
+/// ascending temperature
+///
+/// # Errors
+///
+/// Will return `Err` if the are problems during reading or parsing
+/// of /sys/class/thermal/thermal_zone`zone`/trip_point_*
+pub async fn thermal_trip_points(zone: usize) -> Result<Vec<(String, f32)>> {
+    let mut trip_points = Vec::new();
+
+    for type_path in (glob(&format!(
+        "/sys/class/thermal/thermal_zone{zone}/trip_point_*_type"
+    ))?)
+    .flatten()
+    {
+        let temp_path = PathBuf::from(
+            type_path
+                .to_string_lossy()
+                .trim_end_matches("_type")
+                .to_string()
+                + "_temp",
+        );
+
+        let trip_type = tokio::fs::read_to_string(&type_path)
+            .await
+            .with_context(|| format!("unable to read {}", type_path.display()))?
+            .trim()
+            .to_string();
+        let temp = read_sysfs_thermal(&temp_path).await?;
+
+        trip_points.push((trip_type, temp));
+    }
+
+    trip_points.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    Ok(trip_points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_string_lscpu_formats() {
+        assert_eq!(parse_size_string("32K"), Some(32 * 1024));
+        assert_eq!(parse_size_string("1 MiB"), Some(1024 * 1024));
+        assert_eq!(
+            parse_size_string("8 MiB (1 instance)"),
+            Some(8 * 1024 * 1024)
+        );
+        assert_eq!(
+            parse_size_string("1.5 MiB (12 instances)"),
+            Some(3 * 512 * 1024)
+        );
+    }
+
+    #[test]
+    fn parse_size_string_malformed() {
+        assert_eq!(parse_size_string(""), None);
+        assert_eq!(parse_size_string("32 XiB"), None);
+    }
+
+    #[test]
+    fn parse_cpu_list_ranges_and_singles() {
+        assert_eq!(parse_cpu_list("0-3"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(parse_cpu_list("0,2,4"), Some(vec![0, 2, 4]));
+        assert_eq!(parse_cpu_list("0-1,8-9,16\n"), Some(vec![0, 1, 8, 9, 16]));
+        assert_eq!(parse_cpu_list(""), Some(vec![]));
+    }
+
+    #[test]
+    fn parse_cpu_list_malformed() {
+        assert_eq!(parse_cpu_list("0-"), None);
+        assert_eq!(parse_cpu_list("a,b"), None);
+    }
+
+    #[test]
+    fn numa_node_cpus_with_gaps() {
+        let lscpu_output = serde_json::json!({
+            "NUMA node(s)": "2",
+            "NUMA node0 CPU(s)": "0-3",
+            "NUMA node2 CPU(s)": "4-7",
+        });
+
+        assert_eq!(
+            numa_node_cpus(&lscpu_output),
+            BTreeMap::from([(0, vec![0, 1, 2, 3]), (2, vec![4, 5, 6, 7])])
+        );
+    }
+
+    #[test]
+    fn proc_stat_line_with_offline_core() {
+        // cpu3 is offline, so cpu4 is the fourth core line
+        let proc_stat = "cpu  400 0 200 8000 0 0 0 0 0 0
+cpu0 100 0 50 2000 0 0 0 0 0 0
+cpu1 100 0 50 2000 0 0 0 0 0 0
+cpu2 100 0 50 2000 0 0 0 0 0 0
+cpu4 100 0 50 2000 0 0 0 0 0 0
+intr 12345
+ctxt 67890
+";
+
+        assert_eq!(
+            proc_stat_line(proc_stat, None).unwrap(),
+            "cpu  400 0 200 8000 0 0 0 0 0 0"
+        );
+        assert_eq!(
+            proc_stat_line(proc_stat, Some(2)).unwrap(),
+            "cpu2 100 0 50 2000 0 0 0 0 0 0"
+        );
+        assert_eq!(
+            proc_stat_line(proc_stat, Some(4)).unwrap(),
+            "cpu4 100 0 50 2000 0 0 0 0 0 0"
+        );
+        assert!(proc_stat_line(proc_stat, Some(3)).is_err());
+    }
+
+    /// Creates a fake /sys/class/hwmon below the temporary directory with the
+    /// given hwmons as pairs of their name and temp1_input
+    fn fake_hwmon_base(test: &str, hwmons: &[(&str, &str)]) -> PathBuf {
+        let base = std::env::temp_dir().join(format!("resources-{test}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&base);
+        for (i, (name, temp)) in hwmons.iter().enumerate() {
+            let hwmon = base.join(format!("hwmon{i}"));
+            std::fs::create_dir_all(&hwmon).unwrap();
+            std::fs::write(hwmon.join("name"), format!("{name}\n")).unwrap();
+            std::fs::write(hwmon.join("temp1_input"), format!("{temp}\n")).unwrap();
+        }
+        std::fs::create_dir_all(&base).unwrap();
+        base
+    }
+
+    #[test]
+    fn cpu_temperature_from_hwmon() {
+        let base = fake_hwmon_base(
+            "cpu-temperature",
+            &[("nvme", "38850"), ("k10temp", "52125"), ("amdgpu", "45000")],
+        );
+        let temperature = cpu_temperature_from(&base);
+        std::fs::remove_dir_all(&base).unwrap();
+        assert_eq!(temperature.unwrap(), 52.125);
+    }
+
+    #[test]
+    fn cpu_temperature_from_preferred_hwmon() {
+        let base = fake_hwmon_base(
+            "cpu-temperature-preferred",
+            &[("k10temp", "52125"), ("zenpower", "49000")],
+        );
+        let temperature = cpu_temperature_from(&base);
+        std::fs::remove_dir_all(&base).unwrap();
+        assert_eq!(temperature.unwrap(), 49.0);
+    }
+
+    #[test]
+    fn cpu_temperature_from_without_cpu_hwmon() {
+        let base = fake_hwmon_base("cpu-temperature-none", &[("nvme", "38850")]);
+        let temperature = cpu_temperature_from(&base);
+        std::fs::remove_dir_all(&base).unwrap();
+        assert!(temperature.is_err());
+    }
+
+    #[test]
+    fn cpu_info_to_text_skips_unknown_fields() {
+        let info = CPUInfo {
+            model_name: Some("AMD Ryzen 7 5800X 8-Core Processor".into()),
+            architecture: Some("x86_64".into()),
+            logical_cpus: Some(16),
+            max_speed: Some(4_200_000_000.0),
+            ..Default::default()
+        };
+        assert_eq!(
+            cpu_info_to_text(&info),
+            "Model: AMD Ryzen 7 5800X 8-Core Processor\n\
+             Architecture: x86_64\n\
+             Cores/Threads: ?/16\n\
+             Max Speed: 4.20 GHz"
+        );
+    }
+
+    #[test]
+    fn cpu_info_to_text_all_fields() {
+        let info = CPUInfo {
+            vendor_id: Some("GenuineIntel".into()),
+            model_name: Some("Intel(R) Xeon(R) Gold 6230".into()),
+            architecture: Some("x86_64".into()),
+            logical_cpus: Some(80),
+            physical_cpus: Some(40),
+            sockets: Some(2),
+            virtualization: Some("VT-x".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            cpu_info_to_text(&info),
+            "Model: Intel(R) Xeon(R) Gold 6230\n\
+             Vendor: GenuineIntel\n\
+             Architecture: x86_64\n\
+             Cores/Threads: 40/80\n\
+             Sockets: 2\n\
+             Virtualization: VT-x"
+        );
+    }
+
+    #[test]
+    fn cpu_info_to_text_nothing_known() {
+        assert_eq!(cpu_info_to_text(&CPUInfo::default()), "");
+    }
+
+    #[test]
+    fn average_cpu_freq_skips_unknown() {
+        assert_eq!(
+            average_cpu_freq(&[Some(2_000_000_000), None, Some(4_000_000_000)]),
+            Some(3_000_000_000)
+        );
+        assert_eq!(average_cpu_freq(&[None, None]), None);
+        assert_eq!(average_cpu_freq(&[]), None);
+    }
+
+    #[test]
+    fn cpu_freq_ratio_in_hz() {
+        assert_eq!(
+            cpu_freq_ratio(2_000_000_000, Some(4_000_000_000.0)),
+            Some(0.5)
+        );
+    }
+
+    #[test]
+    fn cpu_freq_ratio_unknown_max() {
+        assert_eq!(cpu_freq_ratio(2_000_000_000, None), None);
+        assert_eq!(cpu_freq_ratio(2_000_000_000, Some(0.0)), None);
+    }
+
+    #[test]
+    fn cpu_freq_ratio_clamps() {
+        // boost clocks can exceed the advertised maximum
+        assert_eq!(
+            cpu_freq_ratio(5_000_000_000, Some(4_000_000_000.0)),
+            Some(1.0)
+        );
+        assert_eq!(cpu_freq_ratio(0, Some(4_000_000_000.0)), Some(0.0));
+    }
+
+    #[test]
+    fn virtualization_from_cpuinfo_intel() {
+        let cpuinfo =
+            "processor\t: 0\nvendor_id\t: GenuineIntel\nflags\t\t: fpu vme de pse vmx smx est\n";
+        assert_eq!(
+            virtualization_from_cpuinfo(cpuinfo),
+            Some("VT-x".to_string())
+        );
+    }
+
+    #[test]
+    fn virtualization_from_cpuinfo_amd() {
+        let cpuinfo =
+            "processor\t: 0\nvendor_id\t: AuthenticAMD\nflags\t\t: fpu vme de pse svm extapic\n";
+        assert_eq!(
+            virtualization_from_cpuinfo(cpuinfo),
+            Some("AMD-V".to_string())
+        );
+    }
+
+    #[test]
+    fn virtualization_from_cpuinfo_without_extension() {
+        // e.g. inside a VM without nested virtualization or on ARM, where
+        // there is no "flags" line at all
+        assert_eq!(
+            virtualization_from_cpuinfo("processor\t: 0\nflags\t\t: fpu vme de pse\n"),
+            None
+        );
+        assert_eq!(
+            virtualization_from_cpuinfo("processor\t: 0\nFeatures\t: fp asimd evtstrm\n"),
+            None
+        );
+    }
+}
@@ -1,20 +1,133 @@
 use anyhow::{Context, Result};
+use glob::glob;
 use gtk::gio::{Icon, ThemedIcon};
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::{
     collections::HashMap,
+    fmt, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use crate::i18n::{i18n, i18n_f};
 
 use super::units::convert_storage;
 
-const SYS_STATS: &str = r" *(?P<read_ios>[0-9]*) *(?P<read_merges>[0-9]*) *(?P<read_sectors>[0-9]*) *(?P<read_ticks>[0-9]*) *(?P<write_ios>[0-9]*) *(?P<write_merges>[0-9]*) *(?P<write_sectors>[0-9]*) *(?P<write_ticks>[0-9]*) *(?P<in_flight>[0-9]*) *(?P<io_ticks>[0-9]*) *(?P<time_in_queue>[0-9]*) *(?P<discard_ios>[0-9]*) *(?P<discard_merges>[0-9]*) *(?P<discard_sectors>[0-9]*) *(?P<discard_ticks>[0-9]*) *(?P<flush_ios>[0-9]*) *(?P<flush_ticks>[0-9]*)";
+// Kernels before 4.18 only report the first 11 fields, the discard fields were added in 4.18
+// and the flush fields in 5.5, so the latter two groups are optional
+const SYS_STATS: &str = r" *(?P<read_ios>[0-9]+) +(?P<read_merges>[0-9]+) +(?P<read_sectors>[0-9]+) +(?P<read_ticks>[0-9]+) +(?P<write_ios>[0-9]+) +(?P<write_merges>[0-9]+) +(?P<write_sectors>[0-9]+) +(?P<write_ticks>[0-9]+) +(?P<in_flight>[0-9]+) +(?P<io_ticks>[0-9]+) +(?P<time_in_queue>[0-9]+)(?: +(?P<discard_ios>[0-9]+) +(?P<discard_merges>[0-9]+) +(?P<discard_sectors>[0-9]+) +(?P<discard_ticks>[0-9]+))?(?: +(?P<flush_ios>[0-9]+) +(?P<flush_ticks>[0-9]+))?";
 
 static RE_DRIVE: Lazy<Regex> = Lazy::new(|| Regex::new(SYS_STATS).unwrap());
 
+/// The size of the sectors counted in /sys/block/<device>/stat, which is always
+/// 512 bytes regardless of the drive's actual sector size
+pub const STAT_SECTOR_SIZE: usize = 512;
+
+/// Errors that can occur when reading the stats of a drive
+#[derive(Debug)]
+pub enum DriveError {
+    /// The drive doesn't exist (anymore), e.g. because it has been unplugged
+    /// since the drives were listed
+    NotFound,
+    /// The stats couldn't be read for another reason
+    Io(io::Error),
+    /// The stats have an unexpected format
+    Parse(String),
+}
+
+impl fmt::Display for DriveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DriveError::NotFound => write!(f, "drive not found"),
+            DriveError::Io(err) => write!(f, "unable to read drive stats: {err}"),
+            DriveError::Parse(file) => write!(f, "unable to parse {file}"),
+        }
+    }
+}
+
+impl std::error::Error for DriveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DriveError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for DriveError {
+    fn from(err: io::Error) -> Self {
+        if err.kind() == io::ErrorKind::NotFound {
+            DriveError::NotFound
+        } else {
+            DriveError::Io(err)
+        }
+    }
+}
+
+/// Read and write throughput of a drive between two readings of its stats
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DriveActivity {
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+}
+
+impl DriveActivity {
+    /// Calculates the throughput between two readings of `Drive::sys_stats()`
+    /// that were taken `elapsed` apart, with `sector_size` being the size of
+    /// the counted sectors in bytes.
+    ///
+    /// Returns `None` if either reading lacks the sector counts or no time
+    /// has passed between them
+    #[must_use]
+    pub fn from_stats(
+        old_stats: &HashMap<String, usize>,
+        new_stats: &HashMap<String, usize>,
+        elapsed: Duration,
+        sector_size: usize,
+    ) -> Option<Self> {
+        let seconds = elapsed.as_secs_f64();
+        if seconds <= 0.0 {
+            return None;
+        }
+
+        let delta = |key: &str| -> Option<f64> {
+            let sectors = new_stats.get(key)?.saturating_sub(*old_stats.get(key)?);
+            Some(sectors.saturating_mul(sector_size) as f64 / seconds)
+        };
+
+        Some(Self {
+            read_bytes_per_sec: delta("read_sectors")?,
+            write_bytes_per_sec: delta("write_sectors")?,
+        })
+    }
+}
+
+/// A mounted filesystem that resides on a drive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Partition {
+    pub block_device: String,
+    pub mount_point: PathBuf,
+    pub total_space: u64,
+    pub available_space: u64,
+}
+
+impl Partition {
+    /// Returns how full the filesystem is as a fraction between 0.0 and 1.0,
+    /// where the space reserved for root is counted as used
+    pub fn used_fraction(&self) -> f64 {
+        if self.total_space == 0 {
+            return 0.0;
+        }
+        1.0 - (self.available_space as f64 / self.total_space as f64)
+    }
+
+    /// Returns whether the filesystem's usage has crossed `threshold` percent
+    pub fn is_near_full(&self, threshold: i32) -> bool {
+        self.used_fraction() * 100.0 >= f64::from(threshold)
+    }
+}
+
 #[derive(Debug)]
 pub struct DriveData {
     pub inner: Drive,
@@ -23,25 +136,43 @@ pub struct DriveData {
     pub removable: bool,
     pub disk_stats: HashMap<String, usize>,
     pub capacity: u64,
+    pub partitions: Vec<Partition>,
+    /// In °C, `None` if the drive has no temperature sensor
+    pub temperature: Option<f32>,
 }
 
 impl DriveData {
-    pub async fn new(path: &Path) -> Self {
+    /// Gathers the data of the drive at `path`. Values that can't be read are
+    /// left at their defaults.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err(DriveError::NotFound)` if the drive has disappeared in
+    /// the meantime, so that it can be removed instead of showing stale data
+    pub async fn new(path: &Path) -> Result<Self, DriveError> {
         let inner = Drive::from_sysfs(&path).await.unwrap_or_default();
+        let disk_stats = match inner.sys_stats().await {
+            Ok(disk_stats) => disk_stats,
+            Err(DriveError::NotFound) => return Err(DriveError::NotFound),
+            Err(_) => HashMap::default(),
+        };
         let is_virtual = inner.is_virtual().await;
         let writable = inner.writable().await.unwrap_or_default();
         let removable = inner.removable().await.unwrap_or_default();
-        let disk_stats = inner.sys_stats().await.unwrap_or_default();
         let capacity = inner.capacity().await.unwrap_or_default();
+        let partitions = inner.partitions().await.unwrap_or_default();
+        let temperature = inner.temperature().await.unwrap_or_default();
 
-        Self {
+        Ok(Self {
             inner,
             is_virtual,
             writable,
             removable,
             disk_stats,
             capacity,
-        }
+            partitions,
+            temperature,
+        })
     }
 }
 
@@ -143,27 +274,13 @@ impl Drive {
     ///
     /// # Errors
     ///
-    /// Will return `Err` if the are errors during
-    /// reading or parsing
-    pub async fn sys_stats(&self) -> Result<HashMap<String, usize>> {
-        let stat = tokio::fs::read_to_string(self.sysfs_path.join("stat"))
-            .await
-            .with_context(|| format!("unable to read /sys/block/{}/stat", self.block_device))?;
+    /// Will return `Err(DriveError::NotFound)` if the drive has disappeared
+    /// and other variants if there are errors during reading or parsing
+    pub async fn sys_stats(&self) -> Result<HashMap<String, usize>, DriveError> {
+        let stat = tokio::fs::read_to_string(self.sysfs_path.join("stat")).await?;
 
-        let captures = RE_DRIVE
-            .captures(&stat)
-            .with_context(|| format!("unable to parse /sys/block/{}/stat", self.block_device))?;
-
-        Ok(RE_DRIVE
-            .capture_names()
-            .flatten()
-            .filter_map(|named_capture| {
-                Some((
-                    named_capture.to_string(),
-                    captures.name(named_capture)?.as_str().parse().ok()?,
-                ))
-            })
-            .collect())
+        parse_sys_stats(&stat)
+            .ok_or_else(|| DriveError::Parse(format!("/sys/block/{}/stat", self.block_device)))
     }
 
     async fn drive_type(&self) -> Result<DriveType> {
@@ -207,6 +324,15 @@ impl Drive {
         }
     }
 
+    /// Returns the temperature of the drive in °C, see `drive_temperature()`
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the sensor exists but couldn't be read or parsed
+    pub async fn temperature(&self) -> Result<Option<f32>> {
+        drive_temperature(&self.block_device).await
+    }
+
     /// Returns, whether the drive is removable
     ///
     /// # Errors
@@ -276,6 +402,78 @@ impl Drive {
             .with_context(|| "unable to parse wwid sysfs file")
     }
 
+    /// Returns the mounted filesystems that reside on this drive or one of its
+    /// partitions, along with their current usage
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the are errors during
+    /// reading or parsing
+    pub async fn partitions(&self) -> Result<Vec<Partition>> {
+        let mounts = tokio::fs::read_to_string("/proc/mounts")
+            .await
+            .with_context(|| "unable to read /proc/mounts")?;
+
+        let mut partitions: Vec<Partition> = Vec::new();
+
+        for line in mounts.lines() {
+            let mut split = line.split_whitespace();
+            let (Some(device), Some(mount_point)) = (split.next(), split.next()) else {
+                continue;
+            };
+
+            if !device.starts_with("/dev/") {
+                continue;
+            }
+
+            // resolves symlinks like /dev/mapper/root -> /dev/dm-0
+            let Some(block_device) = tokio::fs::canonicalize(device).await.ok().and_then(|path| {
+                path.file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+            }) else {
+                continue;
+            };
+
+            let belongs_to_drive =
+                block_device == self.block_device || self.sysfs_path.join(&block_device).exists();
+
+            // the same filesystem may be mounted several times (bind mounts, btrfs subvolumes)
+            if !belongs_to_drive
+                || partitions
+                    .iter()
+                    .any(|partition| partition.block_device == block_device)
+            {
+                continue;
+            }
+
+            let mount_point = PathBuf::from(Self::unescape_mount_point(mount_point));
+
+            let Ok(stat) = nix::sys::statvfs::statvfs(mount_point.as_path()) else {
+                continue;
+            };
+
+            let fragment_size = stat.fragment_size() as u64;
+
+            partitions.push(Partition {
+                block_device,
+                mount_point,
+                total_space: stat.blocks() as u64 * fragment_size,
+                available_space: stat.blocks_available() as u64 * fragment_size,
+            });
+        }
+
+        Ok(partitions)
+    }
+
+    /// /proc/mounts escapes whitespace and backslashes as octal sequences
+    fn unescape_mount_point(mount_point: &str) -> String {
+        mount_point
+            .replace("\\040", " ")
+            .replace("\\011", "\t")
+            .replace("\\012", "\n")
+            .replace("\\134", "\\")
+    }
+
     /// Returns the appropriate Icon for the type of drive
     pub fn icon(&self) -> Icon {
         match self.drive_type {
@@ -312,3 +510,102 @@ impl Drive {
         ThemedIcon::new("unknown-drive-type-symbolic").into()
     }
 }
+
+/// Returns the temperature of the block device `dev` (e.g. "sda" or
+/// "nvme0n1") in °C, read from the hwmon of its device. SATA drives only have
+/// one if the drivetemp module is loaded, NVMe controllers have their own.
+///
+/// Returns `Ok(None)` if there is no temperature sensor.
+///
+/// # Errors
+///
+/// Will return `Err` if the sensor exists but couldn't be read or parsed
+pub async fn drive_temperature(dev: &str) -> Result<Option<f32>> {
+    let device_path = Path::new("/sys/block").join(dev).join("device");
+
+    // drivetemp's hwmons are in a "hwmon" subdirectory of the device, those
+    // of NVMe controllers are directly in the controller's directory
+    let Some(temp_path) = ["hwmon/hwmon*/temp1_input", "hwmon*/temp1_input"]
+        .iter()
+        .flat_map(|pattern| glob(&device_path.join(pattern).to_string_lossy()))
+        .flatten()
+        .flatten()
+        .next()
+    else {
+        return Ok(None);
+    };
+
+    let millidegrees = tokio::fs::read_to_string(&temp_path)
+        .await
+        .with_context(|| format!("unable to read {}", temp_path.display()))?
+        .trim()
+        .parse::<f32>()
+        .with_context(|| format!("unable to parse {}", temp_path.display()))?;
+
+    Ok(Some(millidegrees / 1000.0))
+}
+
+/// Parses the contents of /sys/block/<device>/stat, fields that the kernel
+/// doesn't report are simply left out
+fn parse_sys_stats(stat: &str) -> Option<HashMap<String, usize>> {
+    let captures = RE_DRIVE.captures(stat)?;
+
+    Some(
+        RE_DRIVE
+            .capture_names()
+            .flatten()
+            .filter_map(|named_capture| {
+                Some((
+                    named_capture.to_string(),
+                    captures.name(named_capture)?.as_str().parse().ok()?,
+                ))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_sys_stats_with_11_fields() {
+        let stats = parse_sys_stats(
+            "    4316     1042   373686     2191     3870     2611   117936     4236        0     5364     6427\n",
+        )
+        .unwrap();
+
+        let mut keys = stats.keys().map(String::as_str).collect::<Vec<_>>();
+        keys.sort_unstable();
+        assert_eq!(
+            keys,
+            [
+                "in_flight",
+                "io_ticks",
+                "read_ios",
+                "read_merges",
+                "read_sectors",
+                "read_ticks",
+                "time_in_queue",
+                "write_ios",
+                "write_merges",
+                "write_sectors",
+                "write_ticks",
+            ]
+        );
+        assert_eq!(stats["read_sectors"], 373_686);
+        assert_eq!(stats["time_in_queue"], 6427);
+    }
+
+    #[test]
+    fn parse_sys_stats_with_17_fields() {
+        let stats = parse_sys_stats(
+            "    4316     1042   373686     2191     3870     2611   117936     4236        0     5364     6427        12        0     8192        3      410      155\n",
+        )
+        .unwrap();
+
+        assert_eq!(stats.len(), 17);
+        assert_eq!(stats["discard_sectors"], 8192);
+        assert_eq!(stats["flush_ticks"], 155);
+    }
+}
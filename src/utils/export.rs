@@ -0,0 +1,84 @@
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::app::AppItem;
+use super::units::{convert_cpu_usage, convert_storage};
+
+/// File formats the list of applications can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Guesses the format from the extension of `path`, defaulting to CSV
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        match path
+            .as_ref()
+            .extension()
+            .and_then(|extension| extension.to_str())
+        {
+            Some(extension) if extension.eq_ignore_ascii_case("json") => ExportFormat::Json,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+/// Quotes `field` if it contains characters that would otherwise break the
+/// CSV structure
+fn csv_field<S: AsRef<str>>(field: S) -> String {
+    let field = field.as_ref();
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn apps_csv(items: &[AppItem]) -> String {
+    let mut csv = String::from("name,id,memory_bytes,memory,cpu_ratio,cpu,processes\n");
+
+    for item in items {
+        let row = [
+            csv_field(&item.display_name),
+            csv_field(item.id.as_deref().unwrap_or_default()),
+            item.memory_usage.to_string(),
+            csv_field(convert_storage(item.memory_usage as f64, false)),
+            item.cpu_time_ratio.to_string(),
+            csv_field(convert_cpu_usage(item.cpu_time_ratio)),
+            item.processes_amount.to_string(),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+fn apps_json(items: &[AppItem]) -> Value {
+    items
+        .iter()
+        .map(|item| {
+            json!({
+                "name": item.display_name,
+                "id": item.id,
+                "memory_usage": item.memory_usage,
+                "cpu_usage": item.cpu_time_ratio,
+                "processes": item.processes_amount,
+            })
+        })
+        .collect()
+}
+
+/// Serializes `items` in the given `format`.
+///
+/// The CSV variant contains both the raw values and human-readable ones that
+/// respect the user's unit preferences, the JSON variant only the raw values.
+pub fn export_app_items(items: &[AppItem], format: ExportFormat) -> String {
+    match format {
+        ExportFormat::Csv => apps_csv(items),
+        ExportFormat::Json => serde_json::to_string_pretty(&apps_json(items)).unwrap_or_default(),
+    }
+}
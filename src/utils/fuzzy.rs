@@ -0,0 +1,52 @@
+/// Bonus for every character of the query that was found
+const MATCH_SCORE: i32 = 16;
+/// Bonus for a character that directly follows the previously matched one
+const CONSECUTIVE_BONUS: i32 = 24;
+/// Bonus for a character at the start of the text or of a word within it
+const WORD_START_BONUS: i32 = 12;
+/// Penalty for every character skipped between two matched characters
+const GAP_PENALTY: i32 = 1;
+
+/// Returns how well `query` matches `text`, ignoring case, with higher scores
+/// meaning better matches.
+///
+/// All characters of `query` have to appear in `text` in the same order, but
+/// not necessarily next to each other, so "ffx" matches "Firefox". Returns
+/// `None` if that's not the case. An empty `query` matches everything with a
+/// score of 0.
+#[must_use]
+pub fn fuzzy_score<S: AsRef<str>, T: AsRef<str>>(query: S, text: T) -> Option<i32> {
+    let query: Vec<char> = query.as_ref().to_lowercase().chars().collect();
+    let text: Vec<char> = text.as_ref().to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut query_index = 0;
+    let mut last_match: Option<usize> = None;
+
+    for (text_index, character) in text.iter().enumerate() {
+        if query_index == query.len() {
+            break;
+        }
+
+        if *character != query[query_index] {
+            continue;
+        }
+
+        score += MATCH_SCORE;
+
+        if text_index == 0 || !text[text_index - 1].is_alphanumeric() {
+            score += WORD_START_BONUS;
+        }
+
+        match last_match {
+            Some(last_match) if last_match + 1 == text_index => score += CONSECUTIVE_BONUS,
+            Some(last_match) => score -= (text_index - last_match - 1) as i32 * GAP_PENALTY,
+            None => (),
+        }
+
+        last_match = Some(text_index);
+        query_index += 1;
+    }
+
+    (query_index == query.len()).then_some(score)
+}
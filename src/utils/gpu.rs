@@ -34,6 +34,8 @@ pub struct GpuData {
     pub vram_speed: Option<f64>,
 
     pub temp: Option<f64>,
+    /// Hotspot temperature, only reported by some AMD GPUs
+    pub temp_junction: Option<f64>,
 
     pub power_usage: Option<f64>,
     pub power_cap: Option<f64>,
@@ -55,6 +57,7 @@ impl GpuData {
         let vram_speed = gpu.get_vram_speed().await.ok();
 
         let temp = gpu.get_gpu_temp().await.ok();
+        let temp_junction = gpu.get_gpu_junction_temp().await.ok();
 
         let power_usage = gpu.get_power_usage().await.ok();
         let power_cap = gpu.get_power_cap().await.ok();
@@ -67,6 +70,7 @@ impl GpuData {
             clock_speed,
             vram_speed,
             temp,
+            temp_junction,
             power_usage,
             power_cap,
             power_cap_max,
@@ -398,6 +402,31 @@ impl GPU {
         bail!("no device")
     }
 
+    /// Returns the hotspot temperature of the GPU in °C, whereas
+    /// `get_gpu_temp()` returns the edge temperature
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the GPU isn't an AMD GPU or has no sensor labeled
+    /// "junction"
+    pub async fn get_gpu_junction_temp(&self) -> Result<f64> {
+        if self.device.map(|dev| dev.vendor().id()) != Some(VID_AMD) {
+            bail!("unimplemented");
+        }
+
+        let hwmon = self.hwmon_paths.first().context("no hwmon")?;
+        for i in 1..=3 {
+            let label = tokio::fs::read_to_string(hwmon.join(format!("temp{i}_label")))
+                .await
+                .unwrap_or_default();
+            if label.trim() == "junction" {
+                return Ok(self.read_hwmon_int(0, format!("temp{i}_input")).await? as f64 / 1000.0);
+            }
+        }
+
+        bail!("no junction temperature sensor")
+    }
+
     async fn get_amd_power_usage(&self) -> Result<f64> {
         Ok(self.read_hwmon_int(0, "power1_average").await? as f64 / 1_000_000.0)
     }
@@ -6,6 +6,7 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use serde_json::Value;
 
+use super::pressure::{self, PressureResource, PressureStats};
 use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
 
 static RE_SPEED: Lazy<Regex> = Lazy::new(|| Regex::new(r"Speed: (\d+) MT/s").unwrap());
@@ -20,6 +21,8 @@ pub struct MemoryData {
     pub free_mem: usize,
     pub total_swap: usize,
     pub free_swap: usize,
+    /// `None` if the kernel doesn't support pressure stall information
+    pub pressure: Option<PressureStats>,
 }
 
 impl MemoryData {
@@ -66,14 +69,27 @@ impl MemoryData {
             .unwrap()
             * 1000;
 
+        let pressure = pressure::read_pressure(PressureResource::Memory).await.ok();
+
         Self {
             total_mem,
             available_mem,
             free_mem,
             total_swap,
             free_swap,
+            pressure,
         }
     }
+
+    /// Returns the amount of memory that is in use, i.e. not available for
+    /// starting new applications without swapping
+    pub fn used_mem(&self) -> usize {
+        self.total_mem.saturating_sub(self.available_mem)
+    }
+
+    pub fn used_swap(&self) -> usize {
+        self.total_swap.saturating_sub(self.free_swap)
+    }
 }
 
 async fn proc_meminfo() -> Result<Value, anyhow::Error> {
@@ -84,6 +100,53 @@ async fn proc_meminfo() -> Result<Value, anyhow::Error> {
         .map_err(anyhow::Error::msg)
 }
 
+/// The system-wide memory and swap totals from /proc/meminfo, in bytes
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemInfo {
+    pub total: usize,
+    pub free: usize,
+    pub available: usize,
+    pub buffers: usize,
+    pub cached: usize,
+    pub swap_total: usize,
+    pub swap_free: usize,
+}
+
+/// Reads the system-wide memory totals, see `MemInfo`
+///
+/// # Errors
+///
+/// Will return `Err` if /proc/meminfo can't be read or lacks one of the values
+pub async fn mem_info() -> Result<MemInfo> {
+    let meminfo = tokio::fs::read_to_string("/proc/meminfo")
+        .await
+        .context("unable to read /proc/meminfo")?;
+    parse_meminfo(&meminfo)
+}
+
+/// Parses the contents of /proc/meminfo, whose values are given in kB (i.e.
+/// KiB) like "MemTotal:       16318412 kB"
+fn parse_meminfo(meminfo: &str) -> Result<MemInfo> {
+    let value = |key: &str| -> Result<usize> {
+        meminfo
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+            .and_then(|value| value.split_whitespace().next()?.parse::<usize>().ok())
+            .map(|kilobytes| kilobytes * 1024)
+            .with_context(|| format!("no {key} in /proc/meminfo"))
+    };
+
+    Ok(MemInfo {
+        total: value("MemTotal")?,
+        free: value("MemFree")?,
+        available: value("MemAvailable")?,
+        buffers: value("Buffers")?,
+        cached: value("Cached")?,
+        swap_total: value("SwapTotal")?,
+        swap_free: value("SwapFree")?,
+    })
+}
+
 pub async fn get_total_memory() -> Option<usize> {
     proc_meminfo().await.ok()?["MemTotal"]
         .as_str()
@@ -192,3 +255,43 @@ pub fn pkexec_get_memory_devices() -> Result<Vec<MemoryDevice>> {
     };
     Ok(parse_dmidecode(String::from_utf8(output.stdout)?.as_str()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEMINFO: &str = "MemTotal:       16318412 kB
+MemFree:         1234567 kB
+MemAvailable:    9876543 kB
+Buffers:          456789 kB
+Cached:          5678901 kB
+SwapCached:        12345 kB
+Active:          6789012 kB
+SwapTotal:       8388604 kB
+SwapFree:        8000000 kB
+Hugepagesize:       2048 kB
+";
+
+    #[test]
+    fn parse_meminfo_in_bytes() {
+        assert_eq!(
+            parse_meminfo(MEMINFO).unwrap(),
+            MemInfo {
+                total: 16_318_412 * 1024,
+                free: 1_234_567 * 1024,
+                available: 9_876_543 * 1024,
+                buffers: 456_789 * 1024,
+                // not to be confused with SwapCached
+                cached: 5_678_901 * 1024,
+                swap_total: 8_388_604 * 1024,
+                swap_free: 8_000_000 * 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_meminfo_missing_value() {
+        let meminfo = MEMINFO.replace("MemAvailable", "MemUnavailable");
+        assert!(parse_meminfo(&meminfo).is_err());
+    }
+}
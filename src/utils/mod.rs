@@ -3,13 +3,19 @@ use ini::Ini;
 use once_cell::sync::Lazy;
 
 pub mod app;
+pub mod battery;
 pub mod cpu;
 pub mod drive;
+pub mod export;
+pub mod fuzzy;
 pub mod gpu;
 pub mod memory;
 pub mod network;
+pub mod pressure;
 pub mod process;
 pub mod settings;
+pub mod smoothing;
+pub mod snapshot;
 pub mod units;
 
 // Adapted from Mission Center: https://gitlab.com/mission-center-devs/mission-center/
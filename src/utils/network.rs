@@ -1,6 +1,7 @@
 use std::{
     collections::HashMap,
     ffi::OsString,
+    net::{IpAddr, SocketAddrV4, SocketAddrV6},
     path::{Path, PathBuf},
 };
 
@@ -17,6 +18,7 @@ pub struct NetworkData {
     pub received_bytes: usize,
     pub sent_bytes: usize,
     pub display_name: String,
+    pub addresses: Vec<IpAddr>,
 }
 
 impl NetworkData {
@@ -26,6 +28,8 @@ impl NetworkData {
         let received_bytes = inner.received_bytes().await.unwrap();
         let sent_bytes = inner.sent_bytes().await.unwrap();
         let display_name = inner.display_name();
+        let addresses =
+            interface_addresses(inner.interface_name.to_string_lossy()).unwrap_or_default();
 
         Self {
             inner,
@@ -33,10 +37,46 @@ impl NetworkData {
             received_bytes,
             sent_bytes,
             display_name,
+            addresses,
         }
     }
 }
 
+/// Returns the IPv4 and IPv6 addresses that are assigned to the network
+/// interface called `interface_name`
+///
+/// # Errors
+///
+/// Will return `Err` if the addresses of the system's network interfaces
+/// couldn't be retrieved
+pub fn interface_addresses<S: AsRef<str>>(interface_name: S) -> Result<Vec<IpAddr>> {
+    let addresses = nix::ifaddrs::getifaddrs()
+        .with_context(|| "unable to get addresses of network interfaces")?
+        .filter(|interface_address| interface_address.interface_name == interface_name.as_ref())
+        .filter_map(|interface_address| {
+            let address = interface_address.address?;
+            if let Some(ipv4) = address.as_sockaddr_in() {
+                Some(IpAddr::V4(*SocketAddrV4::from(*ipv4).ip()))
+            } else {
+                address
+                    .as_sockaddr_in6()
+                    .map(|ipv6| IpAddr::V6(*SocketAddrV6::from(*ipv6).ip()))
+            }
+        })
+        .collect();
+
+    Ok(addresses)
+}
+
+/// Returns whether `address` is an IPv6 link-local address (fe80::/10),
+/// which is only valid within the local network segment
+pub fn is_link_local(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(_) => false,
+        IpAddr::V6(ipv6) => (ipv6.segments()[0] & 0xffc0) == 0xfe80,
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub enum InterfaceType {
     Bluetooth,
@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static RE_PRESSURE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(some|full) avg10=([\d.]+) avg60=([\d.]+) avg300=([\d.]+)").unwrap());
+
+/// A resource the kernel keeps pressure stall information (PSI) for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PressureResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PressureResource {
+    fn file_name(self) -> &'static str {
+        match self {
+            PressureResource::Cpu => "cpu",
+            PressureResource::Memory => "memory",
+            PressureResource::Io => "io",
+        }
+    }
+}
+
+/// Percentages of time in which tasks were stalled, averaged over the last
+/// 10, 60 and 300 seconds
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PressureStats {
+    /// Time in which at least some tasks were stalled
+    pub some: PressureAverages,
+    /// Time in which all non-idle tasks were stalled at the same time, not
+    /// reported for the CPU by kernels older than 5.13
+    pub full: Option<PressureAverages>,
+}
+
+impl PressureStats {
+    fn parse<S: AsRef<str>>(pressure: S) -> Option<Self> {
+        let mut some = None;
+        let mut full = None;
+
+        for captures in RE_PRESSURE.captures_iter(pressure.as_ref()) {
+            let averages = PressureAverages {
+                avg10: captures[2].parse().ok()?,
+                avg60: captures[3].parse().ok()?,
+                avg300: captures[4].parse().ok()?,
+            };
+            match &captures[1] {
+                "some" => some = Some(averages),
+                _ => full = Some(averages),
+            }
+        }
+
+        Some(Self { some: some?, full })
+    }
+}
+
+/// Returns the pressure stall information about `resource`.
+///
+/// The system-wide values in /proc/pressure are preferred, the values of our
+/// own cgroup are used if those are unavailable (e.g. in some containers).
+///
+/// # Errors
+///
+/// Will return `Err` if PSI is not supported by the kernel or if the
+/// corresponding files could not be parsed
+pub async fn read_pressure(resource: PressureResource) -> Result<PressureStats> {
+    let file_name = resource.file_name();
+
+    let pressure = match tokio::fs::read_to_string(format!("/proc/pressure/{file_name}")).await {
+        Ok(pressure) => pressure,
+        Err(_) => tokio::fs::read_to_string(format!("/sys/fs/cgroup/{file_name}.pressure"))
+            .await
+            .with_context(|| {
+                format!("unable to read pressure stall information for {file_name}")
+            })?,
+    };
+
+    PressureStats::parse(pressure)
+        .with_context(|| format!("unable to parse pressure stall information for {file_name}"))
+}
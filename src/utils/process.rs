@@ -1,16 +1,281 @@
 use anyhow::{bail, Context, Result};
 use config::LIBEXECDIR;
-use glob::glob;
-use process_data::{Containerization, ProcessData};
+use hashbrown::{HashMap, HashSet};
+use nix::errno::Errno;
+use nix::sys::signal::Signal;
+use once_cell::sync::Lazy;
+use process_data::{
+    AllProcessData, CgroupKind, Containerization, ProcessData, OPEN_FDS_ARG, READ_TIMEOUT,
+};
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::Duration;
+use strum_macros::FromRepr;
 
 use gtk::gio::{Icon, ThemedIcon};
-use tokio::task::JoinSet;
 
 use crate::config;
+use crate::i18n::i18n;
 
 use super::{FLATPAK_APP_PATH, FLATPAK_SPAWN, IS_FLATPAK};
 
+// Maps well-known TCP ports to their service names, e.g. 443 to "https"
+static SERVICE_NAMES: Lazy<HashMap<u16, String>> = Lazy::new(|| {
+    let mut service_names = HashMap::new();
+    for line in std::fs::read_to_string("/etc/services")
+        .unwrap_or_default()
+        .lines()
+    {
+        let mut fields = line.split_whitespace();
+        if let (Some(name), Some(port)) = (fields.next(), fields.next()) {
+            if name.starts_with('#') {
+                continue;
+            }
+            if let Some(port) = port.strip_suffix("/tcp").and_then(|port| port.parse().ok()) {
+                service_names
+                    .entry(port)
+                    .or_insert_with(|| name.to_string());
+            }
+        }
+    }
+    service_names
+});
+
+// Clock ticks per second, the unit procfs uses for times like a process' start time
+static CLOCK_TICKS: Lazy<u64> = Lazy::new(|| {
+    nix::unistd::sysconf(nix::unistd::SysconfVar::CLK_TCK)
+        .ok()
+        .flatten()
+        .and_then(|ticks| u64::try_from(ticks).ok())
+        .filter(|ticks| *ticks > 0)
+        .unwrap_or(100)
+});
+
+/// Returns the time since the system has been booted
+///
+/// # Errors
+///
+/// Will return `Err` if /proc/uptime couldn't be read or parsed
+pub fn system_uptime() -> Result<Duration> {
+    let uptime = std::fs::read_to_string("/proc/uptime").context("unable to read /proc/uptime")?;
+    let seconds = uptime
+        .split_whitespace()
+        .next()
+        .and_then(|seconds| seconds.parse::<f64>().ok())
+        .context("unable to parse /proc/uptime")?;
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+/// Interpreters whose processes are better described by the script they run
+/// than by the interpreter's own name
+const INTERPRETERS: &[&str] = &[
+    "python", "node", "nodejs", "ruby", "perl", "bash", "sh", "zsh", "java",
+];
+
+const NODE_OPTIONS_WITH_VALUES: &[&str] = &[
+    "-r",
+    "--require",
+    "--import",
+    "--loader",
+    "--experimental-loader",
+    "-C",
+    "--conditions",
+    "--title",
+];
+
+/// Options of the interpreters that take their value as a separate argument,
+/// e.g. the "dev" in `python3 -X dev app.py`, which must not be mistaken for
+/// the script
+const INTERPRETER_OPTIONS_WITH_VALUES: &[(&str, &[&str])] = &[
+    ("python", &["-X", "-W", "--check-hash-based-pycs"]),
+    ("node", NODE_OPTIONS_WITH_VALUES),
+    ("nodejs", NODE_OPTIONS_WITH_VALUES),
+    ("ruby", &["-I", "-r", "-C", "-E"]),
+    ("perl", &["-I"]),
+    ("bash", &["-o", "-O"]),
+    ("sh", &["-o"]),
+    ("zsh", &["-o"]),
+];
+
+/// The names of the capabilities, indexed by their number, see capabilities(7)
+const CAPABILITIES: &[&str] = &[
+    "CAP_CHOWN",
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_FOWNER",
+    "CAP_FSETID",
+    "CAP_KILL",
+    "CAP_SETGID",
+    "CAP_SETUID",
+    "CAP_SETPCAP",
+    "CAP_LINUX_IMMUTABLE",
+    "CAP_NET_BIND_SERVICE",
+    "CAP_NET_BROADCAST",
+    "CAP_NET_ADMIN",
+    "CAP_NET_RAW",
+    "CAP_IPC_LOCK",
+    "CAP_IPC_OWNER",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_CHROOT",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_PACCT",
+    "CAP_SYS_ADMIN",
+    "CAP_SYS_BOOT",
+    "CAP_SYS_NICE",
+    "CAP_SYS_RESOURCE",
+    "CAP_SYS_TIME",
+    "CAP_SYS_TTY_CONFIG",
+    "CAP_MKNOD",
+    "CAP_LEASE",
+    "CAP_AUDIT_WRITE",
+    "CAP_AUDIT_CONTROL",
+    "CAP_SETFCAP",
+    "CAP_MAC_OVERRIDE",
+    "CAP_MAC_ADMIN",
+    "CAP_SYSLOG",
+    "CAP_WAKE_ALARM",
+    "CAP_BLOCK_SUSPEND",
+    "CAP_AUDIT_READ",
+    "CAP_PERFMON",
+    "CAP_BPF",
+    "CAP_CHECKPOINT_RESTORE",
+];
+
+/// Capabilities that on their own are enough to gain full control over the
+/// system, e.g. by loading kernel modules or tracing other processes
+const DANGEROUS_CAPABILITIES: &[&str] = &[
+    "CAP_DAC_OVERRIDE",
+    "CAP_DAC_READ_SEARCH",
+    "CAP_SETUID",
+    "CAP_SETGID",
+    "CAP_SETFCAP",
+    "CAP_NET_ADMIN",
+    "CAP_SYS_MODULE",
+    "CAP_SYS_RAWIO",
+    "CAP_SYS_PTRACE",
+    "CAP_SYS_ADMIN",
+    "CAP_MAC_ADMIN",
+    "CAP_BPF",
+];
+
+/// Decodes a capability bit mask as found in /proc/[pid]/status into the names
+/// of the capabilities, ordered by their number. Bits of capabilities newer
+/// than this list are named by their number, e.g. "CAP_41".
+pub fn capability_names(mask: u64) -> Vec<String> {
+    (0..u64::BITS)
+        .filter(|bit| mask & (1 << bit) != 0)
+        .map(|bit| {
+            CAPABILITIES
+                .get(bit as usize)
+                .map_or_else(|| format!("CAP_{bit}"), |name| (*name).to_string())
+        })
+        .collect()
+}
+
+// The number of the newest capability the running kernel knows about, older
+// kernels don't set the bits of capabilities they don't support
+static CAP_LAST_CAP: Lazy<u32> = Lazy::new(|| {
+    std::fs::read_to_string("/proc/sys/kernel/cap_last_cap")
+        .ok()
+        .and_then(|last_cap| last_cap.trim().parse().ok())
+        .unwrap_or(CAPABILITIES.len() as u32 - 1)
+});
+
+/// Whether `mask` contains every capability supported by the running kernel,
+/// which is the case for processes running as root
+pub fn has_all_capabilities(mask: u64) -> bool {
+    has_capabilities_up_to(mask, *CAP_LAST_CAP)
+}
+
+/// Whether `mask` contains every capability from 0 up to and including
+/// `last_cap`
+fn has_capabilities_up_to(mask: u64, last_cap: u32) -> bool {
+    let all = u64::MAX >> (u64::BITS - 1 - last_cap.min(u64::BITS - 1));
+    mask & all == all
+}
+
+/// Returns the names of the capabilities in `mask` that are listed in
+/// `DANGEROUS_CAPABILITIES`
+pub fn dangerous_capabilities(mask: u64) -> Vec<String> {
+    capability_names(mask)
+        .into_iter()
+        .filter(|name| DANGEROUS_CAPABILITIES.contains(&name.as_str()))
+        .collect()
+}
+
+/// Returns the state and the start time (in clock ticks after boot) from the
+/// contents of /proc/[pid]/stat. The comm may contain spaces and parentheses, so
+/// the fields are only split after its closing parenthesis.
+fn parse_stat_state_and_start_time(stat: &str) -> Option<(char, u64)> {
+    let mut fields = stat.rsplit_once(')')?.1.split_whitespace();
+    let state = fields.next()?.chars().next()?;
+    // the state is the 3rd field and the start time the 22nd
+    let start_time = fields.nth(18)?.parse().ok()?;
+    Some((state, start_time))
+}
+
+/// See `Process::script_name()`, `commandline` is the NUL-separated content of
+/// /proc/[pid]/cmdline
+fn script_name(executable_name: &str, commandline: &str) -> Option<String> {
+    // strip version suffixes such as in "python3.11" or "python3"
+    let interpreter = executable_name.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if !INTERPRETERS.contains(&interpreter) {
+        return None;
+    }
+
+    let mut args = commandline
+        .split('\0')
+        .skip(1)
+        .filter(|arg| !arg.is_empty());
+
+    let script = if interpreter == "java" {
+        let mut script = None;
+        while let Some(arg) = args.next() {
+            if arg == "-jar" {
+                script = args.next();
+                break;
+            } else if arg == "-cp" || arg == "-classpath" || arg == "--class-path" {
+                args.next();
+            } else if !arg.starts_with('-') {
+                // the main class
+                script = Some(arg);
+                break;
+            }
+        }
+        script
+    } else {
+        let mut script = None;
+        while let Some(arg) = args.next() {
+            if arg == "-m" && interpreter == "python" {
+                // a module, such as in "python3 -m http.server"
+                script = args.next();
+                break;
+            } else if arg == "-c" || arg == "-e" {
+                // inline code, there is no meaningful name to display
+                break;
+            } else if INTERPRETER_OPTIONS_WITH_VALUES
+                .iter()
+                .any(|(name, options)| *name == interpreter && options.contains(&arg))
+            {
+                // skip the option's value
+                args.next();
+            } else if !arg.starts_with('-') {
+                script = Some(arg);
+                break;
+            }
+        }
+        script
+    }?;
+
+    script
+        .split('/')
+        .nth_back(0)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+}
+
 /// Represents a process that can be found within procfs.
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct Process {
@@ -19,11 +284,15 @@ pub struct Process {
     pub executable_name: String,
     pub icon: Icon,
     pub cpu_time_last: u64,
+    pub user_cpu_time_last: u64,
+    pub system_cpu_time_last: u64,
     pub cpu_time_last_timestamp: u64,
     pub read_bytes_last: Option<u64>,
     pub read_bytes_last_timestamp: Option<u64>,
     pub write_bytes_last: Option<u64>,
     pub write_bytes_last_timestamp: Option<u64>,
+    pub net_bytes_last: Option<u64>,
+    pub net_bytes_last_timestamp: Option<u64>,
 }
 
 // TODO: Better name?
@@ -33,7 +302,197 @@ pub enum ProcessAction {
     STOP,
     KILL,
     CONT,
+    /// Sends an arbitrary signal
+    SIGNAL(Signal),
+}
+
+impl ProcessAction {
+    /// The argument resources-kill expects for this action
+    pub(crate) fn kill_arg(self) -> &'static str {
+        match self {
+            ProcessAction::TERM => "TERM",
+            ProcessAction::STOP => "STOP",
+            ProcessAction::KILL => "KILL",
+            ProcessAction::CONT => "CONT",
+            ProcessAction::SIGNAL(signal) => signal.as_str(),
+        }
+    }
+}
+
+/// Logs the outcome of sending `action` to `pid` so that failures can be
+/// traced in bug reports. It's logged at the debug level, so it's only shown
+/// with `RUST_LOG=debug`.
+fn log_process_action(pid: i32, action: ProcessAction, elevated: bool, result: &Result<()>) {
+    let privileges = if elevated {
+        " with elevated privileges"
+    } else {
+        ""
+    };
+    match result {
+        Ok(()) => log::debug!("Sent {} to PID {pid}{privileges}", action.kill_arg()),
+        Err(error) => log::debug!(
+            "Sending {} to PID {pid}{privileges} failed (errno: {}): {error:#}",
+            action.kill_arg(),
+            error
+                .downcast_ref::<Errno>()
+                .map_or_else(|| "none".to_string(), |errno| format!("{errno:?}")),
+        ),
+    }
+}
+
+/// Executes `action` on all processes with the given `pids` through pkexec, so
+/// that the user is asked to authenticate once for all of them
+fn pkexec_execute_process_action(action: &str, kill_path: &str, pids: &[i32]) -> Result<()> {
+    let pids_args = pids.iter().map(ToString::to_string).collect::<Vec<_>>();
+
+    let mut command = if *IS_FLATPAK {
+        let mut command = Command::new(FLATPAK_SPAWN);
+        command.args(["--host", "pkexec"]);
+        command
+    } else {
+        Command::new("pkexec")
+    };
+    let status_code = command
+        .args(["--disable-internal-agent", kill_path, action])
+        .args(&pids_args)
+        .output()?
+        .status
+        .code()
+        .with_context(|| "no status code?")?;
+
+    if status_code == 0 || status_code == 3 {
+        // 0 := successful; 3 := process not found which we don't care
+        // about because that might happen because we killed the
+        // process' parent first, killing the child before we explicitly do
+        Ok(())
+    } else {
+        // 126 := authentication dismissed; 127 := not authorized
+        let errno = match status_code {
+            126 | 127 => Errno::EPERM,
+            _ => Errno::from_i32(status_code),
+        };
+        Err(errno).with_context(|| {
+            format!(
+                "couldn't kill {} with elevated privileges due to unknown reasons, status code: {}",
+                pids_args.join(", "),
+                status_code
+            )
+        })
+    }
+}
+
+/// Returns whether `error` was caused by missing permissions, i.e. whether
+/// retrying with elevated privileges might help
+pub fn is_permission_error(error: &anyhow::Error) -> bool {
+    matches!(
+        error.downcast_ref::<Errno>(),
+        Some(Errno::EPERM | Errno::EACCES)
+    )
+}
+
+fn kill_path() -> String {
+    if *IS_FLATPAK {
+        format!(
+            "{}/libexec/resources/resources-kill",
+            FLATPAK_APP_PATH.as_str()
+        )
+    } else {
+        format!("{LIBEXECDIR}/resources-kill")
+    }
+}
+
+/// The state of a process according to the third field of /proc/[pid]/stat.
+/// The variants are ordered by how alarming they are, so that the maximum of
+/// several states is the one that should be shown for all of them.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, FromRepr)]
+pub enum ProcessState {
+    #[default]
+    Unknown,
+    Idle,
+    Sleeping,
+    Running,
+    Stopped,
+    /// Waiting in uninterruptible sleep, usually for I/O ("D")
+    UninterruptibleSleep,
+    /// Has ended but hasn't been reaped by its parent yet
+    Zombie,
+}
+
+impl ProcessState {
+    #[must_use]
+    pub fn from_stat(state: char) -> Self {
+        match state {
+            'R' => ProcessState::Running,
+            'S' => ProcessState::Sleeping,
+            'D' => ProcessState::UninterruptibleSleep,
+            'Z' => ProcessState::Zombie,
+            'T' | 't' => ProcessState::Stopped,
+            'I' => ProcessState::Idle,
+            _ => ProcessState::Unknown,
+        }
+    }
+
+    #[must_use]
+    pub fn name(self) -> String {
+        match self {
+            ProcessState::Unknown => i18n("Unknown"),
+            ProcessState::Idle => i18n("Idle"),
+            ProcessState::Sleeping => i18n("Sleeping"),
+            ProcessState::Running => i18n("Running"),
+            ProcessState::Stopped => i18n("Halted"),
+            ProcessState::UninterruptibleSleep => i18n("Uninterruptible"),
+            ProcessState::Zombie => i18n("Zombie"),
+        }
+    }
 }
+
+/// How trustworthy a process appears to be, mainly used for color-coding
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustLevel {
+    /// The process runs inside a sandbox like Flatpak
+    Sandboxed,
+    Normal,
+    /// The process runs with root privileges or holds dangerous capabilities
+    Elevated,
+}
+
+/// A search for the processes that use some resource
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReverseLookup {
+    /// Processes that have a TCP socket listening on the port, `None` if the
+    /// search isn't a valid port
+    ListeningOn(Option<u16>),
+    /// Processes that have the file open
+    OpenFile(PathBuf),
+}
+
+impl ReverseLookup {
+    /// Searches starting with ":" look for processes listening on a port,
+    /// searches starting with "/" for processes that have a file open, any
+    /// other search isn't a reverse lookup
+    pub fn from_search(search: &str) -> Option<Self> {
+        if let Some(port) = search.strip_prefix(':') {
+            Some(Self::ListeningOn(port.trim().parse().ok()))
+        } else if search.starts_with('/') {
+            Some(Self::OpenFile(PathBuf::from(search.trim())))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the PIDs of the matching processes. This reads the file
+    /// descriptors of every process, so it shouldn't be called on the main
+    /// thread.
+    pub fn pids(&self) -> HashSet<i32> {
+        match self {
+            Self::ListeningOn(Some(port)) => Process::pids_listening_on(*port),
+            Self::ListeningOn(None) => HashSet::new(),
+            Self::OpenFile(path) => Process::pids_with_open_file(path),
+        }
+    }
+}
+
 /// Convenience struct for displaying running processes
 #[derive(Debug, Clone)]
 pub struct ProcessItem {
@@ -43,54 +502,95 @@ pub struct ProcessItem {
     pub icon: Icon,
     pub memory_usage: usize,
     pub cpu_time_ratio: f32,
+    pub user_cpu_ratio: f32,
+    pub system_cpu_ratio: f32,
     pub commandline: String,
+    /// The individual arguments of the commandline, empty for kernel threads
+    pub cmdline: Vec<String>,
     pub containerization: Containerization,
     pub cgroup: Option<String>,
+    pub cgroup_kind: CgroupKind,
     pub read_speed: Option<f64>,
     pub read_total: Option<u64>,
     pub write_speed: Option<f64>,
     pub write_total: Option<u64>,
+    pub minor_faults: u64,
+    pub major_faults: u64,
+    pub wchan: Option<String>,
+    pub state: ProcessState,
+    pub effective_capabilities: u64,
+    /// See `Process::is_elevated()`
+    pub elevated: bool,
+    pub nice: i32,
+    pub swap_usage: usize,
+    /// See `ProcessData::open_fds`
+    pub open_fds: Option<usize>,
+    /// See `Process::start_time()`
+    pub start_time: Duration,
+    /// See `Process::net_speed()`
+    pub net_speed: Option<f64>,
+}
+
+impl ProcessItem {
+    /// Returns what kind of unit or container the process' cgroup belongs
+    /// to, `None` if it's none of the known ones
+    pub fn cgroup_kind_name(&self) -> Option<String> {
+        match self.cgroup_kind {
+            CgroupKind::SystemService => Some(i18n("System Service")),
+            CgroupKind::UserService => Some(i18n("User Service")),
+            CgroupKind::Scope => Some(i18n("Scope")),
+            CgroupKind::Flatpak => Some(i18n("Flatpak")),
+            CgroupKind::Podman => Some(i18n("Podman Container")),
+            CgroupKind::Docker => Some(i18n("Docker Container")),
+            CgroupKind::Other => None,
+        }
+    }
+
+    pub fn trust_level(&self) -> TrustLevel {
+        if self.containerization == Containerization::Flatpak {
+            TrustLevel::Sandboxed
+        } else if self.elevated {
+            TrustLevel::Elevated
+        } else {
+            TrustLevel::Normal
+        }
+    }
 }
 
 impl Process {
-    /// Returns a `Vec` containing all currently running processes.
+    /// Returns the data of all currently running processes, counting their
+    /// open file descriptors only if `count_open_fds` is set.
     ///
     /// # Errors
     ///
     /// Will return `Err` if there are problems traversing and
     /// parsing procfs
-    pub async fn all_data() -> Result<Vec<ProcessData>> {
+    pub async fn all_data(count_open_fds: bool) -> Result<AllProcessData> {
         if *IS_FLATPAK {
             let proxy_path = format!(
                 "{}/libexec/resources/resources-processes",
                 FLATPAK_APP_PATH.as_str()
             );
-            let command = async_process::Command::new(FLATPAK_SPAWN)
-                .args(["--host", proxy_path.as_str()])
-                .output()
-                .await?;
+            // the helper bounds every read by `READ_TIMEOUT` itself, allow for
+            // spawning it on top of that but don't let a stuck helper stall
+            // the refresh task
+            let command = tokio::time::timeout(
+                READ_TIMEOUT * 2,
+                async_process::Command::new(FLATPAK_SPAWN)
+                    .args(["--host", proxy_path.as_str()])
+                    .args(count_open_fds.then_some(OPEN_FDS_ARG))
+                    .kill_on_drop(true)
+                    .output(),
+            )
+            .await
+            .context("process helper timed out")??;
             let output = command.stdout;
-            let proxy_output: Vec<ProcessData> =
-                rmp_serde::from_slice::<Vec<ProcessData>>(&output)?;
+            let proxy_output: AllProcessData = rmp_serde::from_slice::<AllProcessData>(&output)?;
 
             return Ok(proxy_output);
         }
 
-        let mut tasks = JoinSet::new();
-        for entry in glob("/proc/[0-9]*/").context("unable to glob")?.flatten() {
-            tasks.spawn(ProcessData::try_from_path(entry));
-        }
-
-        let mut process_data = Vec::with_capacity(tasks.len());
-        while let Some(task) = tasks.join_next().await {
-            // Unwrap is fine because the runtime stays alive
-            // Nothing should be able to panic here
-            if let Ok(data) = task.unwrap() {
-                process_data.push(data);
-            }
-        }
-
-        Ok(process_data)
+        ProcessData::all(count_open_fds).await
     }
 
     pub fn from_process_data(process_data: ProcessData) -> Self {
@@ -119,38 +619,42 @@ impl Process {
             (None, None)
         };
 
+        let (net_bytes_last, net_bytes_last_timestamp) = if process_data.net_bytes.is_some() {
+            (Some(0), Some(0))
+        } else {
+            (None, None)
+        };
+
         Self {
             executable_path,
             executable_name,
             data: process_data,
             icon: ThemedIcon::new("generic-process").into(),
             cpu_time_last: 0,
+            user_cpu_time_last: 0,
+            system_cpu_time_last: 0,
             cpu_time_last_timestamp: 0,
             read_bytes_last,
             read_bytes_last_timestamp,
             write_bytes_last,
             write_bytes_last_timestamp,
+            net_bytes_last,
+            net_bytes_last_timestamp,
         }
     }
 
     pub fn execute_process_action(&self, action: ProcessAction) -> Result<()> {
-        let action_str = match action {
-            ProcessAction::TERM => "TERM",
-            ProcessAction::STOP => "STOP",
-            ProcessAction::KILL => "KILL",
-            ProcessAction::CONT => "CONT",
-        };
+        let result = self.send_process_action(action);
+        log_process_action(self.data.pid, action, false, &result);
+        result
+    }
+
+    fn send_process_action(&self, action: ProcessAction) -> Result<()> {
+        let action_str = action.kill_arg();
 
         // TODO: tidy this mess up
 
-        let kill_path = if *IS_FLATPAK {
-            format!(
-                "{}/libexec/resources/resources-kill",
-                FLATPAK_APP_PATH.as_str()
-            )
-        } else {
-            format!("{LIBEXECDIR}/resources-kill")
-        };
+        let kill_path = kill_path();
 
         let status_code = if *IS_FLATPAK {
             Command::new(FLATPAK_SPAWN)
@@ -181,73 +685,150 @@ impl Process {
             Ok(())
         } else if status_code == 1 {
             // 1 := no permissions
-            self.pkexec_execute_process_action(action_str, &kill_path)
+            pkexec_execute_process_action(action_str, &kill_path, &[self.data.pid])
         } else {
-            bail!(
-                "couldn't kill {} due to unknown reasons, status code: {}",
-                self.data.pid,
-                status_code
-            )
+            // resources-kill exits with the errno of the failed kill(2)
+            Err(Errno::from_i32(status_code)).with_context(|| {
+                format!(
+                    "couldn't kill {} due to unknown reasons, status code: {}",
+                    self.data.pid, status_code
+                )
+            })
         }
     }
 
-    fn pkexec_execute_process_action(&self, action: &str, kill_path: &str) -> Result<()> {
-        let status_code = if *IS_FLATPAK {
-            Command::new(FLATPAK_SPAWN)
+    /// Executes `action` with elevated privileges right away, used to retry
+    /// after an attempt failed because of missing permissions.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if authentication was dismissed or denied, the
+    /// error's source is then `Errno::EPERM`
+    pub fn execute_process_action_elevated(&self, action: ProcessAction) -> Result<()> {
+        Self::execute_process_action_elevated_pids(&[self.data.pid], action)
+    }
+
+    /// Executes `action` on all processes with the given `pids` in a single
+    /// elevated call, so that the user only has to authenticate once. This
+    /// waits for the authentication, so don't call it on the main thread.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if authentication was dismissed or denied, the
+    /// error's source is then `Errno::EPERM`
+    pub fn execute_process_action_elevated_pids(pids: &[i32], action: ProcessAction) -> Result<()> {
+        let result = pkexec_execute_process_action(action.kill_arg(), &kill_path(), pids);
+        for pid in pids {
+            log_process_action(*pid, action, true, &result);
+        }
+        result
+    }
+
+    #[must_use]
+    pub fn cpu_time_ratio(&self) -> f32 {
+        if self.cpu_time_last == 0 {
+            0.0
+        } else {
+            self.ratio_since_last(self.data.cpu_time, self.cpu_time_last)
+        }
+    }
+
+    /// Sets the nice value of the process, lowering it below the current
+    /// value requires elevated privileges.
+    ///
+    /// # Errors
+    ///
+    /// Will return `Err` if the priority couldn't be changed, which is an
+    /// `Errno::EPERM` if it's due to missing privileges
+    pub fn set_nice(&self, nice: i32) -> Result<()> {
+        if *IS_FLATPAK {
+            let status = Command::new(FLATPAK_SPAWN)
                 .args([
                     "--host",
-                    "pkexec",
-                    "--disable-internal-agent",
-                    kill_path,
-                    action,
+                    "renice",
+                    "-n",
+                    nice.to_string().as_str(),
+                    "-p",
                     self.data.pid.to_string().as_str(),
                 ])
                 .output()?
-                .status
-                .code()
-                .with_context(|| "no status code?")?
+                .status;
+
+            if status.success() {
+                Ok(())
+            } else if nice < self.data.nice {
+                // renice doesn't tell us why it failed, but this is by far the most likely reason
+                Err(Errno::EPERM.into())
+            } else {
+                bail!(
+                    "couldn't renice {}, status code: {:?}",
+                    self.data.pid,
+                    status.code()
+                )
+            }
         } else {
-            Command::new("pkexec")
-                .args([
-                    "--disable-internal-agent",
-                    kill_path,
-                    action,
-                    self.data.pid.to_string().as_str(),
-                ])
-                .output()?
-                .status
-                .code()
-                .with_context(|| "no status code?")?
-        };
+            // SAFETY: setpriority() only takes integer arguments and doesn't touch any memory of ours
+            let result = unsafe {
+                nix::libc::setpriority(
+                    nix::libc::PRIO_PROCESS,
+                    self.data.pid as nix::libc::id_t,
+                    nice,
+                )
+            };
+            Errno::result(result)
+                .map(drop)
+                .with_context(|| format!("couldn't renice {}", self.data.pid))
+        }
+    }
 
-        if status_code == 0 || status_code == 3 {
-            // 0 := successful; 3 := process not found which we don't care
-            // about because that might happen because we killed the
-            // process' parent first, killing the child before we explicitly do
-            Ok(())
+    /// Whether the process with `pid` that was started at `start_time` (in
+    /// clock ticks after boot) is still running. Zombies have already ended
+    /// and a different start time means that the PID has been reused.
+    #[must_use]
+    pub fn is_still_running(pid: i32, start_time: u64) -> bool {
+        std::fs::read_to_string(format!("/proc/{pid}/stat"))
+            .ok()
+            .and_then(|stat| parse_stat_state_and_start_time(&stat))
+            .is_some_and(|(state, current_start_time)| {
+                state != 'Z' && current_start_time == start_time
+            })
+    }
+
+    /// Returns the time after boot the process was started at
+    #[must_use]
+    pub fn start_time(&self) -> Duration {
+        Duration::from_millis(self.data.start_time.saturating_mul(1000) / *CLOCK_TICKS)
+    }
+
+    /// Returns the ratio of time spent executing in userspace
+    #[must_use]
+    pub fn user_cpu_ratio(&self) -> f32 {
+        if self.cpu_time_last == 0 {
+            0.0
         } else {
-            bail!(
-                "couldn't kill {} with elevated privileges due to unknown reasons, status code: {}",
-                self.data.pid,
-                status_code
-            )
+            self.ratio_since_last(self.data.user_cpu_time, self.user_cpu_time_last)
         }
     }
 
+    /// Returns the ratio of time spent executing in the kernel, e.g. for syscalls
     #[must_use]
-    pub fn cpu_time_ratio(&self) -> f32 {
+    pub fn system_cpu_ratio(&self) -> f32 {
         if self.cpu_time_last == 0 {
             0.0
         } else {
-            (self.data.cpu_time.saturating_sub(self.cpu_time_last) as f32
-                / (self
-                    .data
-                    .cpu_time_timestamp
-                    .saturating_sub(self.cpu_time_last_timestamp)) as f32)
-                .clamp(0.0, 1.0)
+            self.ratio_since_last(self.data.system_cpu_time, self.system_cpu_time_last)
         }
     }
 
+    fn ratio_since_last(&self, cpu_time: u64, cpu_time_last: u64) -> f32 {
+        (cpu_time.saturating_sub(cpu_time_last) as f32
+            / (self
+                .data
+                .cpu_time_timestamp
+                .saturating_sub(self.cpu_time_last_timestamp)) as f32)
+            .clamp(0.0, 1.0)
+    }
+
     #[must_use]
     pub fn read_speed(&self) -> Option<f64> {
         if let (
@@ -300,6 +881,252 @@ impl Process {
         }
     }
 
+    /// Returns the network throughput of the namespace of this process in
+    /// bytes per second, see `ProcessData::net_bytes` for why this is only an
+    /// estimate and `None` for most processes
+    #[must_use]
+    pub fn net_speed(&self) -> Option<f64> {
+        if let (
+            Some(net_bytes),
+            Some(net_bytes_timestamp),
+            Some(net_bytes_last),
+            Some(net_bytes_last_timestamp),
+        ) = (
+            self.data.net_bytes,
+            self.data.net_bytes_timestamp,
+            self.net_bytes_last,
+            self.net_bytes_last_timestamp,
+        ) {
+            if net_bytes_last_timestamp == 0 {
+                Some(0.0)
+            } else {
+                let bytes_delta = net_bytes.saturating_sub(net_bytes_last) as f64;
+                let time_delta =
+                    net_bytes_timestamp.saturating_sub(net_bytes_last_timestamp) as f64;
+                Some((bytes_delta / time_delta) * 1000.0)
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name of the script (or jar) that is being run if this
+    /// process is a known interpreter, e.g. "myserver.py" for
+    /// `python3 /opt/myserver.py --port 80`. Returns `None` if this process
+    /// is not an interpreter or no script could be determined.
+    pub fn script_name(&self) -> Option<String> {
+        script_name(&self.executable_name, &self.data.commandline)
+    }
+
+    /// Whether the process has root privileges, either by running as root,
+    /// through a setuid binary or by holding capabilities that are as
+    /// powerful, see `dangerous_capabilities()`
+    pub fn is_elevated(&self) -> bool {
+        self.data.uid == 0
+            || self.data.effective_uid == 0
+            || !dangerous_capabilities(self.data.effective_capabilities).is_empty()
+    }
+
+    /// Returns the path of the binary this process runs, `None` if it has
+    /// been deleted (e.g. by an update) or is unknown (e.g. for kernel
+    /// threads)
+    pub fn executable_path(&self) -> Option<&str> {
+        self.data
+            .exe
+            .as_deref()
+            .filter(|exe| !exe.is_empty() && !exe.ends_with(" (deleted)"))
+    }
+
+    /// Returns the path of the binary this process runs, used for grouping
+    /// processes by their executable. Falls back to the process' `comm` if
+    /// there is no `executable_path()`.
+    pub fn executable_group(&self) -> String {
+        self.executable_path()
+            .map_or_else(|| self.data.comm.clone(), str::to_string)
+    }
+
+    /// Returns the remote addresses (e.g. "93.184.216.34:https") of all TCP
+    /// connections of the processes with the given `pids`. This is fairly
+    /// expensive, so only call this when really needed.
+    pub fn remote_addresses(pids: &[i32]) -> Vec<String> {
+        let inodes: HashSet<u64> = pids
+            .iter()
+            .filter_map(|pid| Self::socket_inodes(*pid).ok())
+            .flatten()
+            .collect();
+
+        if inodes.is_empty() {
+            return Vec::new();
+        }
+
+        let mut addresses = Vec::new();
+        for (path, ipv6) in [("/proc/net/tcp", false), ("/proc/net/tcp6", true)] {
+            let Ok(sockets) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            for line in sockets.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if let (Some(remote_address), Some(state), Some(inode)) = (
+                    fields.get(2),
+                    fields.get(3),
+                    fields.get(9).and_then(|inode| inode.parse::<u64>().ok()),
+                ) {
+                    // listening sockets (0A) don't have a remote address
+                    if *state == "0A" || !inodes.contains(&inode) {
+                        continue;
+                    }
+                    if let Some(address) = Self::decode_socket_address(remote_address, ipv6) {
+                        addresses.push(address);
+                    }
+                }
+            }
+        }
+
+        addresses.sort();
+        addresses.dedup();
+        addresses
+    }
+
+    /// Returns the PIDs of all processes that have a TCP socket listening on
+    /// `port`
+    pub fn pids_listening_on(port: u16) -> HashSet<i32> {
+        let mut inodes = HashSet::new();
+        for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+            let Ok(sockets) = std::fs::read_to_string(path) else {
+                continue;
+            };
+            for line in sockets.lines().skip(1) {
+                let fields: Vec<&str> = line.split_whitespace().collect();
+                if let (Some(local_port), Some(state), Some(inode)) = (
+                    fields
+                        .get(1)
+                        .and_then(|address| address.split_once(':'))
+                        .and_then(|(_, port)| u16::from_str_radix(port, 16).ok()),
+                    fields.get(3),
+                    fields.get(9).and_then(|inode| inode.parse::<u64>().ok()),
+                ) {
+                    if *state == "0A" && local_port == port {
+                        inodes.insert(inode);
+                    }
+                }
+            }
+        }
+
+        if inodes.is_empty() {
+            return HashSet::new();
+        }
+
+        Self::all_pids()
+            .filter(|pid| {
+                Self::socket_inodes(*pid)
+                    .map(|socket_inodes| !socket_inodes.is_disjoint(&inodes))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Returns the PIDs of all processes that have the file at `path` open
+    pub fn pids_with_open_file<P: AsRef<Path>>(path: P) -> HashSet<i32> {
+        let path = path.as_ref();
+        Self::all_pids()
+            .filter(|pid| {
+                std::fs::read_dir(format!("/proc/{pid}/fd"))
+                    .map(|fds| {
+                        fds.flatten()
+                            .any(|fd| std::fs::read_link(fd.path()).is_ok_and(|link| link == path))
+                    })
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    fn all_pids() -> impl Iterator<Item = i32> {
+        std::fs::read_dir("/proc")
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+    }
+
+    fn socket_inodes(pid: i32) -> Result<HashSet<u64>> {
+        let mut inodes = HashSet::new();
+        for fd in std::fs::read_dir(format!("/proc/{pid}/fd"))?.flatten() {
+            if let Some(inode) = std::fs::read_link(fd.path()).ok().and_then(|link| {
+                link.to_str()
+                    .and_then(|link| link.strip_prefix("socket:["))
+                    .and_then(|link| link.strip_suffix(']'))
+                    .and_then(|inode| inode.parse().ok())
+            }) {
+                inodes.insert(inode);
+            }
+        }
+        Ok(inodes)
+    }
+
+    /// Decodes an address like "0100007F:01BB" from /proc/net/tcp{,6} into
+    /// "127.0.0.1:https"
+    fn decode_socket_address(address: &str, ipv6: bool) -> Option<String> {
+        let (ip, port) = address.split_once(':')?;
+
+        let port = u16::from_str_radix(port, 16).ok()?;
+        let port = SERVICE_NAMES
+            .get(&port)
+            .cloned()
+            .unwrap_or_else(|| port.to_string());
+
+        // the kernel prints addresses as 32 bit words in host byte order
+        let mut bytes = Vec::with_capacity(16);
+        for i in (0..ip.len()).step_by(8) {
+            let word = u32::from_str_radix(ip.get(i..i + 8)?, 16).ok()?;
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+
+        let ip = if ipv6 {
+            IpAddr::from(<[u8; 16]>::try_from(bytes).ok()?)
+        } else {
+            IpAddr::from(<[u8; 4]>::try_from(bytes).ok()?)
+        };
+
+        match ip {
+            IpAddr::V4(ip) => Some(format!("{ip}:{port}")),
+            IpAddr::V6(ip) => Some(
+                ip.to_ipv4_mapped()
+                    .map_or_else(|| format!("[{ip}]:{port}"), |ip| format!("{ip}:{port}")),
+            ),
+        }
+    }
+
+    /// Splits the NUL-separated contents of /proc/[pid]/cmdline into the
+    /// individual arguments
+    pub fn cmdline_args<S: AsRef<str>>(cmdline: S) -> Vec<String> {
+        cmdline
+            .as_ref()
+            .split_terminator('\0')
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Joins `args` back into a commandline, quoting arguments that would
+    /// otherwise be ambiguous (e.g. ones containing spaces)
+    pub fn format_cmdline_args(args: &[String]) -> String {
+        // processes that rewrite their commandline (e.g. Chromium's helpers)
+        // often put everything into one space-separated argument
+        if let [arg] = args {
+            return arg.clone();
+        }
+
+        args.iter()
+            .map(|arg| {
+                if arg.is_empty() || arg.contains(char::is_whitespace) {
+                    format!("'{}'", arg.replace('\'', "'\\''"))
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
     pub fn sanitize_cmdline<S: AsRef<str>>(cmdline: S) -> Option<String> {
         let cmdline = cmdline.as_ref();
         if cmdline.is_empty() {
@@ -309,3 +1136,175 @@ impl Process {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STAT: &str = "1234 (my (weird) app) S 1 1234 1234 0 -1 4194560 100 0 0 0 5 3 0 0 20 0 1 0 4567 12345678 900 18446744073709551615";
+
+    #[test]
+    fn parse_stat_state_and_start_time_comm_with_parentheses() {
+        assert_eq!(parse_stat_state_and_start_time(STAT), Some(('S', 4567)));
+    }
+
+    #[test]
+    fn parse_stat_state_and_start_time_zombie() {
+        let stat = STAT.replace(") S ", ") Z ");
+        assert_eq!(parse_stat_state_and_start_time(&stat), Some(('Z', 4567)));
+    }
+
+    #[test]
+    fn parse_stat_state_and_start_time_truncated() {
+        assert_eq!(parse_stat_state_and_start_time("1234 (app) S 1 1234"), None);
+        assert_eq!(parse_stat_state_and_start_time(""), None);
+    }
+
+    #[test]
+    fn script_name_of_script() {
+        assert_eq!(
+            script_name("python3", "python3\0/opt/myserver.py\0--port\080\0").as_deref(),
+            Some("myserver.py")
+        );
+        assert_eq!(
+            script_name("python3.11", "python3.11\0-u\0tool.py\0").as_deref(),
+            Some("tool.py")
+        );
+        assert_eq!(
+            script_name("bash", "bash\0./backup.sh\0").as_deref(),
+            Some("backup.sh")
+        );
+    }
+
+    #[test]
+    fn script_name_not_an_interpreter() {
+        assert_eq!(script_name("firefox", "firefox\0/tmp/page.html\0"), None);
+    }
+
+    #[test]
+    fn script_name_without_script() {
+        assert_eq!(script_name("python3", "python3\0"), None);
+        assert_eq!(script_name("node", "node\0--inspect\0"), None);
+        assert_eq!(script_name("bash", "bash\0/tmp/directory/\0"), None);
+    }
+
+    #[test]
+    fn script_name_python_module() {
+        assert_eq!(
+            script_name("python3", "python3\0-m\0http.server\08000\0").as_deref(),
+            Some("http.server")
+        );
+        // -m is only a module for python
+        assert_eq!(
+            script_name("ruby", "ruby\0-m\0server.rb\0").as_deref(),
+            Some("server.rb")
+        );
+    }
+
+    #[test]
+    fn script_name_python_module_missing() {
+        assert_eq!(script_name("python3", "python3\0-m\0"), None);
+    }
+
+    #[test]
+    fn script_name_inline_code() {
+        assert_eq!(script_name("python3", "python3\0-c\0import os\0"), None);
+        assert_eq!(script_name("perl", "perl\0-e\0print 1\0script.pl\0"), None);
+        assert_eq!(script_name("node", "node\0-e\0run()\0"), None);
+    }
+
+    #[test]
+    fn script_name_options_with_values() {
+        assert_eq!(
+            script_name("python3", "python3\0-X\0dev\0-W\0ignore\0app.py\0").as_deref(),
+            Some("app.py")
+        );
+        assert_eq!(
+            script_name("node", "node\0--require\0dotenv/config\0server.js\0").as_deref(),
+            Some("server.js")
+        );
+        assert_eq!(
+            script_name("ruby", "ruby\0-I\0lib\0bin/rails\0").as_deref(),
+            Some("rails")
+        );
+        assert_eq!(
+            script_name("bash", "bash\0-o\0pipefail\0deploy.sh\0").as_deref(),
+            Some("deploy.sh")
+        );
+    }
+
+    #[test]
+    fn script_name_option_value_missing() {
+        assert_eq!(script_name("python3", "python3\0-X\0"), None);
+    }
+
+    #[test]
+    fn script_name_java_jar() {
+        assert_eq!(
+            script_name("java", "java\0-Xmx1g\0-jar\0/opt/app/server.jar\0--debug\0").as_deref(),
+            Some("server.jar")
+        );
+    }
+
+    #[test]
+    fn script_name_java_jar_missing() {
+        assert_eq!(script_name("java", "java\0-jar\0"), None);
+    }
+
+    #[test]
+    fn script_name_java_class_path() {
+        assert_eq!(
+            script_name("java", "java\0-cp\0lib/*\0com.example.Main\0").as_deref(),
+            Some("com.example.Main")
+        );
+        assert_eq!(
+            script_name("java", "java\0--class-path\0app.jar\0-Dfoo=bar\0org.Main\0").as_deref(),
+            Some("org.Main")
+        );
+        assert_eq!(script_name("java", "java\0-classpath\0"), None);
+    }
+
+    #[test]
+    fn capability_names_ordered_by_number() {
+        assert_eq!(
+            capability_names(0b1010_0001),
+            vec!["CAP_CHOWN", "CAP_KILL", "CAP_SETUID"]
+        );
+        assert!(capability_names(0).is_empty());
+    }
+
+    #[test]
+    fn capability_names_unknown_bits() {
+        assert_eq!(
+            capability_names((1 << 40) | (1 << 41)),
+            vec!["CAP_CHECKPOINT_RESTORE", "CAP_41"]
+        );
+    }
+
+    #[test]
+    fn has_capabilities_up_to_older_kernel() {
+        // root on a kernel before 5.8 lacks CAP_PERFMON, CAP_BPF and
+        // CAP_CHECKPOINT_RESTORE
+        assert!(has_capabilities_up_to(0x0000_003f_ffff_ffff, 37));
+        assert!(!has_capabilities_up_to(0x0000_003f_ffff_ffff, 40));
+        assert!(has_capabilities_up_to(0x0000_01ff_ffff_ffff, 40));
+        assert!(!has_capabilities_up_to(0x0000_003f_ffff_fffe, 37));
+    }
+
+    #[test]
+    fn has_capabilities_up_to_full_mask() {
+        assert!(has_capabilities_up_to(u64::MAX, 63));
+        assert!(!has_capabilities_up_to(u64::MAX >> 1, 63));
+        assert!(has_capabilities_up_to(u64::MAX, 100));
+    }
+
+    #[test]
+    fn dangerous_capabilities_filters() {
+        let mask = (1 << 0) | (1 << 7) | (1 << 10) | (1 << 21);
+        assert_eq!(
+            dangerous_capabilities(mask),
+            vec!["CAP_SETUID", "CAP_SYS_ADMIN"]
+        );
+        assert!(dangerous_capabilities(1 << 10).is_empty());
+    }
+}
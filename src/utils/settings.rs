@@ -1,4 +1,4 @@
-use std::{ops::Deref, str::FromStr};
+use std::{collections::HashMap, ops::Deref, str::FromStr};
 
 use adw::prelude::*;
 
@@ -106,6 +106,25 @@ impl RefreshSpeed {
     }
 }
 
+/// How processes are combined into the items of the Applications view
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumString, Display, Hash, FromRepr)]
+pub enum GroupingMode {
+    /// By the installed application they belong to, all others are
+    /// System Processes
+    #[default]
+    Application,
+    /// By the binary they're running
+    Executable,
+}
+
+/// The persisted state of a `ColumnView`: the ID of the column it's sorted by,
+/// whether it's sorted ascendingly and the widths of its resized columns by their IDs
+pub type ColumnsState = (String, bool, HashMap<String, i32>);
+
+/// Memory usages in bytes above which a notification is sent, keyed by app ID
+pub type MemoryAlerts = HashMap<String, u64>;
+
 #[derive(Clone, Debug, Hash)]
 pub struct Settings(gio::Settings);
 
@@ -167,6 +186,35 @@ impl Settings {
         })
     }
 
+    pub fn apps_grouping(&self) -> GroupingMode {
+        GroupingMode::from_str(self.string("apps-grouping").as_str()).unwrap_or_default()
+    }
+
+    pub fn set_apps_grouping(&self, value: GroupingMode) -> Result<(), glib::error::BoolError> {
+        self.set_string("apps-grouping", &value.to_string())
+    }
+
+    pub fn apps_pinned(&self) -> Vec<String> {
+        self.strv("apps-pinned")
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    pub fn set_apps_pinned(&self, value: &[String]) -> Result<(), glib::error::BoolError> {
+        self.set_strv("apps-pinned", value)
+    }
+
+    pub fn connect_apps_pinned<F: Fn(Vec<String>) + 'static>(&self, f: F) -> glib::SignalHandlerId {
+        self.connect_changed(Some("apps-pinned"), move |settings, _key| {
+            f(settings
+                .strv("apps-pinned")
+                .iter()
+                .map(ToString::to_string)
+                .collect());
+        })
+    }
+
     // the following three functions are kept for compatibility reasons and for not having an oddly named function
     // called "set_is_maximized" generated by the macro
     pub fn maximized(&self) -> bool {
@@ -183,7 +231,37 @@ impl Settings {
         })
     }
 
-    int_settings!(window_width, window_height);
+    pub fn applications_columns(&self) -> ColumnsState {
+        self.get("applications-columns")
+    }
+
+    pub fn set_applications_columns(
+        &self,
+        value: &ColumnsState,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_value("applications-columns", &value.to_variant())
+    }
+
+    pub fn apps_memory_alerts(&self) -> MemoryAlerts {
+        self.get("apps-memory-alerts")
+    }
+
+    pub fn set_apps_memory_alerts(
+        &self,
+        value: &MemoryAlerts,
+    ) -> Result<(), glib::error::BoolError> {
+        self.set_value("apps-memory-alerts", &value.to_variant())
+    }
+
+    int_settings!(
+        window_width,
+        window_height,
+        drive_full_threshold,
+        end_grace_period,
+        cpu_precision,
+        apps_idle_cpu_threshold,
+        apps_idle_memory_threshold
+    );
 
     bool_settings!(
         show_search_on_start,
@@ -191,12 +269,26 @@ impl Settings {
         show_virtual_network_interfaces,
         sidebar_details,
         network_bits,
+        memory_show_available,
+        memory_graph_auto_scale,
+        smooth_graphs,
+        color_code_trust,
+        confirm_process_actions,
+        always_confirm_destructive_actions,
         apps_show_memory,
+        apps_show_swap,
         apps_show_cpu,
         apps_show_drive_read_speed,
         apps_show_drive_read_total,
         apps_show_drive_write_speed,
         apps_show_drive_write_total,
+        apps_show_running_since,
+        apps_show_priority,
+        apps_show_open_fds,
+        apps_show_net_speed,
+        apps_show_state,
+        apps_pin_system_processes,
+        apps_hide_idle,
         processes_show_id,
         processes_show_user,
         processes_show_memory,
@@ -205,6 +297,7 @@ impl Settings {
         processes_show_drive_read_total,
         processes_show_drive_write_speed,
         processes_show_drive_write_total,
+        processes_show_cgroup,
         show_logical_cpus
     );
 }
@@ -0,0 +1,38 @@
+use super::settings::SETTINGS;
+
+/// Weight of the newest sample when "Smooth Graphs" is enabled, lower values
+/// smooth more but make the displayed value lag further behind
+pub const SMOOTHING_ALPHA: f64 = 0.4;
+
+/// Returns the alpha that should be used for displayed values according to
+/// the user's preferences, 1.0 means no smoothing at all
+pub fn smoothing_alpha() -> f64 {
+    if SETTINGS.smooth_graphs() {
+        SMOOTHING_ALPHA
+    } else {
+        1.0
+    }
+}
+
+/// Exponential moving average over a series of samples.
+///
+/// The first sample is returned unchanged since there is nothing to blend it
+/// with, so e.g. a newly started app initially shows its raw CPU usage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Ema {
+    value: Option<f64>,
+}
+
+impl Ema {
+    /// Blends `sample` into the average with the weight `alpha` (between 0.0
+    /// and 1.0) and returns the new average
+    pub fn push(&mut self, sample: f64, alpha: f64) -> f64 {
+        let alpha = alpha.clamp(0.0, 1.0);
+        let value = match self.value {
+            Some(previous) => alpha * sample + (1.0 - alpha) * previous,
+            None => sample,
+        };
+        self.value = Some(value);
+        value
+    }
+}
@@ -0,0 +1,180 @@
+use std::time::SystemTime;
+
+use serde_json::{json, Value};
+
+use super::battery;
+use super::cpu;
+use super::drive::{Drive, DriveData};
+use super::memory::MemoryData;
+use super::network::{NetworkData, NetworkInterface};
+use super::pressure::{self, PressureAverages, PressureResource};
+use super::process::{self, ProcessItem};
+
+/// Has to be increased whenever the layout of a snapshot changes in a way
+/// that could break programs reading it
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Returns the JSON representation of `process`, which is run by `user`
+pub fn process_json(process: &ProcessItem, user: &str) -> Value {
+    json!({
+        "pid": process.pid,
+        "uid": process.uid,
+        "user": user,
+        "name": process.display_name,
+        "commandline": process.commandline,
+        "cgroup": process.cgroup,
+        "cgroup_kind": format!("{:?}", process.cgroup_kind),
+        "containerization": format!("{:?}", process.containerization),
+        "cpu_usage": process.cpu_time_ratio,
+        "user_cpu_usage": process.user_cpu_ratio,
+        "system_cpu_usage": process.system_cpu_ratio,
+        "memory_usage": process.memory_usage,
+        "read_speed": process.read_speed,
+        "read_total": process.read_total,
+        "write_speed": process.write_speed,
+        "write_total": process.write_total,
+        "minor_faults": process.minor_faults,
+        "major_faults": process.major_faults,
+        "wchan": process.wchan,
+        "state": format!("{:?}", process.state),
+        "nice": process.nice,
+        "swap_usage": process.swap_usage,
+        "open_fds": process.open_fds,
+        // in seconds after boot
+        "start_time": process.start_time.as_secs_f64(),
+        "effective_capabilities": process::capability_names(process.effective_capabilities),
+        "net_speed": process.net_speed,
+    })
+}
+
+fn pressure_averages_json(averages: &PressureAverages) -> Value {
+    json!({
+        "avg10": averages.avg10,
+        "avg60": averages.avg60,
+        "avg300": averages.avg300,
+    })
+}
+
+/// Returns the pressure stall information of `resource` or `null` if it's
+/// unavailable
+async fn pressure_json(resource: PressureResource) -> Value {
+    match pressure::read_pressure(resource).await {
+        Ok(stats) => json!({
+            "some": pressure_averages_json(&stats.some),
+            "full": stats.full.as_ref().map(pressure_averages_json),
+        }),
+        Err(_) => Value::Null,
+    }
+}
+
+/// Gathers information about the CPU, memory, drives and network interfaces
+/// and bundles them together with the already gathered `processes`.
+///
+/// Usages are taken from a single reading, so they don't contain any values
+/// that would require a comparison with an earlier reading (like drive speeds).
+pub async fn snapshot(processes: Vec<Value>) -> Value {
+    let cpu_info = cpu::cpu_info().await.unwrap_or_default();
+
+    let memory = MemoryData::new().await;
+
+    let mut drives = Vec::new();
+    for path in Drive::get_sysfs_paths().await.unwrap_or_default() {
+        let Ok(drive_data) = DriveData::new(&path).await else {
+            continue;
+        };
+        drives.push(json!({
+            "block_device": drive_data.inner.block_device,
+            "model": drive_data.inner.model,
+            "type": format!("{:?}", drive_data.inner.drive_type),
+            "virtual": drive_data.is_virtual,
+            "writable": drive_data.writable,
+            "removable": drive_data.removable,
+            "capacity": drive_data.capacity,
+            "stats": drive_data.disk_stats,
+            "partitions": drive_data
+                .partitions
+                .iter()
+                .map(|partition| {
+                    json!({
+                        "block_device": partition.block_device,
+                        "mount_point": partition.mount_point.to_string_lossy(),
+                        "total_space": partition.total_space,
+                        "available_space": partition.available_space,
+                    })
+                })
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    let mut network_interfaces = Vec::new();
+    for path in NetworkInterface::get_sysfs_paths()
+        .await
+        .unwrap_or_default()
+    {
+        let network_data = NetworkData::new(&path).await;
+        network_interfaces.push(json!({
+            "name": network_data.inner.interface_name.to_string_lossy(),
+            "type": format!("{:?}", network_data.inner.interface_type),
+            "driver": network_data.inner.driver_name,
+            "vendor": network_data.inner.vendor,
+            "device": network_data.inner.device_name,
+            "hw_address": network_data.inner.hw_address,
+            "speed": network_data.inner.speed,
+            "virtual": network_data.is_virtual,
+            "received_bytes": network_data.received_bytes,
+            "sent_bytes": network_data.sent_bytes,
+            "addresses": network_data.addresses,
+        }));
+    }
+
+    let pressure = json!({
+        "cpu": pressure_json(PressureResource::Cpu).await,
+        "memory": pressure_json(PressureResource::Memory).await,
+        "io": pressure_json(PressureResource::Io).await,
+    });
+
+    let batteries = battery::battery_info()
+        .await
+        .unwrap_or_default()
+        .iter()
+        .map(|battery| {
+            json!({
+                "name": battery.name,
+                "capacity": battery.capacity,
+                "status": format!("{:?}", battery.status),
+                "energy_now": battery.energy_now,
+                "energy_full": battery.energy_full,
+                "power_now": battery.power_now,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let power_supply = json!({
+        "ac_online": battery::ac_online().await,
+        "batteries": batteries,
+    });
+
+    let timestamp = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default();
+
+    json!({
+        "format_version": SNAPSHOT_FORMAT_VERSION,
+        "resources_version": crate::config::VERSION,
+        "timestamp": timestamp,
+        "cpu": serde_json::to_value(&cpu_info).unwrap_or_default(),
+        "memory": {
+            "total": memory.total_mem,
+            "available": memory.available_mem,
+            "free": memory.free_mem,
+            "total_swap": memory.total_swap,
+            "free_swap": memory.free_swap,
+        },
+        "drives": drives,
+        "network_interfaces": network_interfaces,
+        "pressure": pressure,
+        "power_supply": power_supply,
+        "processes": processes,
+    })
+}
@@ -1,7 +1,9 @@
 use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter, EnumString};
 
-use crate::i18n::i18n_f;
+use std::time::Duration;
+
+use crate::i18n::{i18n, i18n_f};
 
 use super::settings::{Base, TemperatureUnit, SETTINGS};
 
@@ -59,77 +61,45 @@ pub fn convert_temperature(celsius: f64) -> String {
 }
 
 pub fn convert_storage(bytes: f64, integer: bool) -> String {
-    match SETTINGS.base() {
-        Base::Decimal => convert_storage_decimal(bytes, integer),
-        Base::Binary => convert_storage_binary(bytes, integer),
-    }
-}
-
-fn convert_storage_decimal(bytes: f64, integer: bool) -> String {
-    let (mut number, prefix) = to_largest_prefix(bytes, Base::Decimal);
-    if integer {
-        number = number.round();
-        match prefix {
-            Prefix::None => i18n_f("{} B", &[&format!("{}", number)]),
-            Prefix::Kilo => i18n_f("{} kB", &[&format!("{}", number)]),
-            Prefix::Mega => i18n_f("{} MB", &[&format!("{}", number)]),
-            Prefix::Giga => i18n_f("{} GB", &[&format!("{}", number)]),
-            Prefix::Tera => i18n_f("{} TB", &[&format!("{}", number)]),
-            Prefix::Peta => i18n_f("{} PB", &[&format!("{}", number)]),
-            Prefix::Exa => i18n_f("{} EB", &[&format!("{}", number)]),
-            Prefix::Zetta => i18n_f("{} ZB", &[&format!("{}", number)]),
-            Prefix::Yotta => i18n_f("{} YB", &[&format!("{}", number)]),
-            Prefix::Ronna => i18n_f("{} RB", &[&format!("{}", number)]),
-            Prefix::Quetta => i18n_f("{} QB", &[&format!("{}", number)]),
-        }
-    } else {
-        match prefix {
-            Prefix::None => i18n_f("{} B", &[&format!("{}", number.round())]),
-            Prefix::Kilo => i18n_f("{} kB", &[&format!("{:.2}", number)]),
-            Prefix::Mega => i18n_f("{} MB", &[&format!("{:.2}", number)]),
-            Prefix::Giga => i18n_f("{} GB", &[&format!("{:.2}", number)]),
-            Prefix::Tera => i18n_f("{} TB", &[&format!("{:.2}", number)]),
-            Prefix::Peta => i18n_f("{} PB", &[&format!("{:.2}", number)]),
-            Prefix::Exa => i18n_f("{} EB", &[&format!("{:.2}", number)]),
-            Prefix::Zetta => i18n_f("{} ZB", &[&format!("{:.2}", number)]),
-            Prefix::Yotta => i18n_f("{} YB", &[&format!("{:.2}", number)]),
-            Prefix::Ronna => i18n_f("{} RB", &[&format!("{:.2}", number)]),
-            Prefix::Quetta => i18n_f("{} QB", &[&format!("{:.2}", number)]),
-        }
-    }
+    format_bytes(bytes, SETTINGS.base(), if integer { 0 } else { 2 })
 }
 
-fn convert_storage_binary(bytes: f64, integer: bool) -> String {
-    let (mut number, prefix) = to_largest_prefix(bytes, Base::Binary);
-    if integer {
-        number = number.round();
-        match prefix {
-            Prefix::None => i18n_f("{} B", &[&format!("{}", number)]),
-            Prefix::Kilo => i18n_f("{} KiB", &[&format!("{}", number)]),
-            Prefix::Mega => i18n_f("{} MiB", &[&format!("{}", number)]),
-            Prefix::Giga => i18n_f("{} GiB", &[&format!("{}", number)]),
-            Prefix::Tera => i18n_f("{} TiB", &[&format!("{}", number)]),
-            Prefix::Peta => i18n_f("{} PiB", &[&format!("{}", number)]),
-            Prefix::Exa => i18n_f("{} EiB", &[&format!("{}", number)]),
-            Prefix::Zetta => i18n_f("{} ZiB", &[&format!("{}", number)]),
-            Prefix::Yotta => i18n_f("{} YiB", &[&format!("{}", number)]),
-            Prefix::Ronna => i18n_f("{} RiB", &[&format!("{}", number)]),
-            Prefix::Quetta => i18n_f("{} QiB", &[&format!("{}", number)]),
-        }
-    } else {
-        match prefix {
-            Prefix::None => i18n_f("{} B", &[&format!("{}", number.round())]),
-            Prefix::Kilo => i18n_f("{} KiB", &[&format!("{:.2}", number)]),
-            Prefix::Mega => i18n_f("{} MiB", &[&format!("{:.2}", number)]),
-            Prefix::Giga => i18n_f("{} GiB", &[&format!("{:.2}", number)]),
-            Prefix::Tera => i18n_f("{} TiB", &[&format!("{:.2}", number)]),
-            Prefix::Peta => i18n_f("{} PiB", &[&format!("{:.2}", number)]),
-            Prefix::Exa => i18n_f("{} EiB", &[&format!("{:.2}", number)]),
-            Prefix::Zetta => i18n_f("{} ZiB", &[&format!("{:.2}", number)]),
-            Prefix::Yotta => i18n_f("{} YiB", &[&format!("{:.2}", number)]),
-            Prefix::Ronna => i18n_f("{} RiB", &[&format!("{:.2}", number)]),
-            Prefix::Quetta => i18n_f("{} QiB", &[&format!("{:.2}", number)]),
-        }
+/// Formats `bytes` with the largest fitting prefix of `base` and `precision`
+/// decimal places, including the unit, e.g. "1.50 GiB". Amounts below one
+/// kilobyte are always shown as whole bytes.
+pub fn format_bytes(bytes: f64, base: Base, precision: usize) -> String {
+    let (number, prefix) = to_largest_prefix(bytes, base);
+    let number = match prefix {
+        Prefix::None => format!("{}", number.round()),
+        _ => format!("{number:.precision$}"),
+    };
+    match base {
+        Base::Decimal => match prefix {
+            Prefix::None => i18n_f("{} B", &[&number]),
+            Prefix::Kilo => i18n_f("{} kB", &[&number]),
+            Prefix::Mega => i18n_f("{} MB", &[&number]),
+            Prefix::Giga => i18n_f("{} GB", &[&number]),
+            Prefix::Tera => i18n_f("{} TB", &[&number]),
+            Prefix::Peta => i18n_f("{} PB", &[&number]),
+            Prefix::Exa => i18n_f("{} EB", &[&number]),
+            Prefix::Zetta => i18n_f("{} ZB", &[&number]),
+            Prefix::Yotta => i18n_f("{} YB", &[&number]),
+            Prefix::Ronna => i18n_f("{} RB", &[&number]),
+            Prefix::Quetta => i18n_f("{} QB", &[&number]),
+        },
+        Base::Binary => match prefix {
+            Prefix::None => i18n_f("{} B", &[&number]),
+            Prefix::Kilo => i18n_f("{} KiB", &[&number]),
+            Prefix::Mega => i18n_f("{} MiB", &[&number]),
+            Prefix::Giga => i18n_f("{} GiB", &[&number]),
+            Prefix::Tera => i18n_f("{} TiB", &[&number]),
+            Prefix::Peta => i18n_f("{} PiB", &[&number]),
+            Prefix::Exa => i18n_f("{} EiB", &[&number]),
+            Prefix::Zetta => i18n_f("{} ZiB", &[&number]),
+            Prefix::Yotta => i18n_f("{} YiB", &[&number]),
+            Prefix::Ronna => i18n_f("{} RiB", &[&number]),
+            Prefix::Quetta => i18n_f("{} QiB", &[&number]),
+        },
     }
 }
 
@@ -253,3 +223,25 @@ pub fn convert_power(watts: f64) -> String {
         Prefix::Quetta => i18n_f("{} QW", &[&format!("{:.2}", number)]),
     }
 }
+
+/// Formats a CPU time ratio as a percentage with as many decimal places as
+/// the user prefers, e.g. "12.3 %" for the columns of the applications and
+/// processes pages
+pub fn convert_cpu_usage(ratio: f32) -> String {
+    let precision = SETTINGS.cpu_precision().clamp(0, 2) as usize;
+    format!("{:.*} %", precision, ratio * 100.0)
+}
+
+pub fn convert_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+    let (days, hours, minutes) = (seconds / 86400, (seconds / 3600) % 24, (seconds / 60) % 60);
+    if days > 0 {
+        i18n_f("{} d {} h", &[&days.to_string(), &hours.to_string()])
+    } else if hours > 0 {
+        i18n_f("{} h {} min", &[&hours.to_string(), &minutes.to_string()])
+    } else if minutes > 0 {
+        i18n_f("{} min", &[&minutes.to_string()])
+    } else {
+        i18n("Less than a minute")
+    }
+}